@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
 
@@ -14,94 +15,124 @@ fn read_file_to_vector(file_path: &str) -> Result<Vec<String>, io::Error> {
 	Ok(lines_vec)
 }
 
-fn play_allowed(color: &str, quantity: &str, max_red: u32, max_green: u32, max_blue: u32) -> bool {
-	match color {
-		"red" => quantity.trim().parse::<u32>().unwrap_or(0) <= max_red,
-		"green" => quantity.trim().parse::<u32>().unwrap_or(0) <= max_green,
-		"blue" => quantity.trim().parse::<u32>().unwrap_or(0) <= max_blue,
-		_ => false,
+// Cube counts keyed by colour name, used both for a single draw and for bag limits
+type Cubes = HashMap<String, u32>;
+
+#[derive(Debug, PartialEq)]
+enum GameError {
+	Malformed(String)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Draw {
+	cubes: Cubes
+}
+
+impl Draw {
+	fn parse(draw: &str) -> Result<Self, GameError> {
+		let mut cubes = Cubes::new();
+
+		for hand in draw.split(", ") {
+			let malformed = || GameError::Malformed(hand.to_string());
+
+			let (quantity, color) = hand.trim().split_once(' ').ok_or_else(malformed)?;
+			let quantity = quantity.parse::<u32>().map_err(|_| malformed())?;
+			let color = color.trim();
+
+			// A colour is a single word, anything else means a separator was missing
+			if color.is_empty() || color.contains(|c: char| c.is_whitespace() || c == ',' || c == ';') {
+				return Err(malformed());
+			}
+
+			*cubes.entry(color.to_string()).or_insert(0) += quantity;
+		}
+
+		Ok(Self { cubes })
+	}
+
+	fn is_possible(&self, bag: &Cubes) -> bool {
+		self.cubes
+			.iter()
+			.all(|(color, &quantity)| quantity <= bag.get(color).copied().unwrap_or(0))
 	}
 }
 
-fn sum_index(games: Vec<String>) -> u32 {
-	games
-		.iter()
-		.map(|game| {
-			game.split(": ").find_map(|word| {
-				if let Some(number) = word.strip_prefix("Game ") {
-					number.parse::<u32>().ok()
-				} else {
-					None
-				}
-			})
+#[derive(Debug, Clone, PartialEq)]
+struct Game {
+	id: u32,
+	draws: Vec<Draw>
+}
+
+impl Game {
+	fn parse(game: &str) -> Result<Self, GameError> {
+		let malformed = || GameError::Malformed(game.to_string());
+
+		let (header, draws) = game.split_once(": ").ok_or_else(malformed)?;
+		let id = header
+			.strip_prefix("Game ")
+			.and_then(|id| id.trim().parse().ok())
+			.ok_or_else(malformed)?;
+
+		Ok(Self {
+			id,
+			draws: draws.split("; ").map(Draw::parse).collect::<Result<_, _>>()?
 		})
-		.filter_map(|number| number)
-		.fold(0, |acc, num| acc + num)
+	}
+
+	fn is_possible(&self, bag: &Cubes) -> bool {
+		self.draws.iter().all(|draw| draw.is_possible(bag))
+	}
+
+	// Fewest cubes of each colour that would make every draw possible. Every one of `colors`
+	// is present, at 0 when the game never drew it, so it still counts towards the power
+	fn minimum_bag(&self, colors: &[&str]) -> Cubes {
+		let empty = colors.iter().map(|&color| (color.to_string(), 0)).collect();
+
+		self.draws
+			.iter()
+			.flat_map(|draw| draw.cubes.iter())
+			.fold(empty, |mut minimum: Cubes, (color, &quantity)| {
+				let entry = minimum.entry(color.clone()).or_insert(0);
+				*entry = (*entry).max(quantity);
+				minimum
+			})
+	}
+
+	fn power(&self, colors: &[&str]) -> u32 {
+		self.minimum_bag(colors).values().product()
+	}
 }
 
-fn filter_games(games: &Vec<String>) -> Vec<String> {
-	games
+fn parse_games(games: &[String]) -> Result<Vec<Game>, GameError> {
+	games.iter().map(|game| Game::parse(game)).collect()
+}
+
+fn bag_from(limits: &[(&str, u32)]) -> Cubes {
+	limits
 		.iter()
-		.filter(|&game| {
-			game.split(": ").skip(1).all(|plays| {
-				plays.split("; ").all(|play| {
-					play.split(", ").collect::<Vec<_>>().iter().all(|colors| {
-						let hand: Vec<_> = colors.split(" ").collect();
-
-						if let (Some(quantity), Some(color)) = (hand.get(0), hand.get(1)) {
-							return play_allowed(color, quantity, 12, 13, 14);
-						} else {
-							false
-						}
-					})
-				})
-			})
-		})
-		.cloned()
+		.map(|&(color, quantity)| (color.to_string(), quantity))
 		.collect()
 }
 
-fn get_min_pieces(games: &Vec<String>) -> Vec<(u32, u32, u32)> {
-	let game_counts: Vec<(u32, u32, u32)> = games
+fn sum_possible_ids(games: &[Game], bag: &Cubes) -> u32 {
+	games
 		.iter()
-		.map(|game| {
-			game.split(": ").nth(1).map_or((0, 0, 0), |part| {
-				part.split("; ")
-					.flat_map(|section| section.split(", "))
-					.fold(
-						(0, 0, 0),
-						|(mut red_count, mut green_count, mut blue_count), count_str| {
-							let parts: Vec<_> = count_str.split_whitespace().collect();
-
-							if parts.len() == 2 {
-								let count = parts[0].parse::<u32>().unwrap_or(0);
-
-								match parts[1] {
-									"red" => red_count = red_count.max(count),
-									"green" => green_count = green_count.max(count),
-									"blue" => blue_count = blue_count.max(count),
-									_ => (),
-								}
-							}
-
-							(red_count, green_count, blue_count)
-						},
-					)
-			})
-		})
-		.collect();
+		.filter(|game| game.is_possible(bag))
+		.map(|game| game.id)
+		.sum()
+}
 
-	game_counts
+fn sum_powers(games: &[Game], colors: &[&str]) -> u32 {
+	games.iter().map(|game| game.power(colors)).sum()
 }
 
 fn main() {
-	let games = read_file_to_vector("input/2023/day2.txt").unwrap();
-	let sum = sum_index(filter_games(&games));
-	println!("{}", sum);
+	let input = read_file_to_vector("input/2023/day2.txt").unwrap();
+	let games = parse_games(&input).unwrap();
+	let bag = bag_from(&[("red", 12), ("green", 13), ("blue", 14)]);
 
-	let min_pieces = get_min_pieces(&games);
-	let power_sum = min_pieces.iter().fold(0, |sum, &(x, y, z)| sum + x * y * z);
-	println!("{}", power_sum);
+	println!("{}", sum_possible_ids(&games, &bag));
+	println!("{}", sum_powers(&games, &["red", "green", "blue"]));
 }
 
 #[cfg(test)]
@@ -110,23 +141,63 @@ mod tests {
 
 	#[test]
 	fn test_color_allowed() {
-		let color = "red";
-		let quantity = "3";
+		let game = Game::parse("Game 1: 3 red").unwrap();
 
-		assert_eq!(play_allowed(color, quantity, 3, 5, 6), true);
+		assert!(game.is_possible(&bag_from(&[("red", 3), ("green", 5), ("blue", 6)])));
 	}
 
 	#[test]
 	fn test_color_not_allowed() {
-		let color = "red";
-		let quantity = "3";
+		let game = Game::parse("Game 1: 3 red").unwrap();
+
+		assert!(!game.is_possible(&bag_from(&[("red", 2), ("green", 5), ("blue", 6)])));
+	}
 
-		assert_eq!(play_allowed(color, quantity, 2, 5, 6), false);
+	#[test]
+	fn test_unknown_color_not_allowed() {
+		let game = Game::parse("Game 1: 1 yellow, 2 red").unwrap();
+
+		assert!(!game.is_possible(&bag_from(&[("red", 12), ("green", 13), ("blue", 14)])));
+		assert!(game.is_possible(&bag_from(&[("red", 2), ("yellow", 1)])));
+	}
+
+	#[test]
+	fn test_power_missing_color() {
+		let game = Game::parse("Game 1: 3 red; 2 green").unwrap();
+		let colors = ["red", "green", "blue"];
+
+		assert_eq!(game.minimum_bag(&colors), bag_from(&[("red", 3), ("green", 2), ("blue", 0)]));
+		assert_eq!(game.power(&colors), 0);
+	}
+
+	#[test]
+	fn test_parse_game() {
+		let game = Game::parse("Game 12: 3 blue, 4 red; 2 green").unwrap();
+
+		assert_eq!(game.id, 12);
+		assert_eq!(game.draws.len(), 2);
+		assert_eq!(game.draws[0].cubes, bag_from(&[("blue", 3), ("red", 4)]));
+		assert_eq!(game.draws[1].cubes, bag_from(&[("green", 2)]));
+	}
+
+	#[test]
+	fn test_parse_malformed() {
+		assert_eq!(
+			Game::parse("Game 2: 3red, x blue"),
+			Err(GameError::Malformed("3red".to_string())));
+		assert_eq!(
+			Game::parse("Game 2: 3 red, x blue"),
+			Err(GameError::Malformed("x blue".to_string())));
+		assert_eq!(
+			Game::parse("Game 1: 3 blue,4 red;2 green"),
+			Err(GameError::Malformed("3 blue,4 red;2 green".to_string())));
+		assert!(Game::parse("Game two: 3 red").is_err());
+		assert!(Game::parse("3 red").is_err());
 	}
 
 	#[test]
 	fn test_sample_1() {
-		let games = [
+		let games: Vec<String> = [
 			"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
 			"Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
 			"Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
@@ -137,13 +208,14 @@ mod tests {
 		.map(|&s| s.to_string())
 		.collect();
 
-		let filtered_games = filter_games(&games);
-		assert_eq!(8, sum_index(filtered_games));
+		let games = parse_games(&games).unwrap();
+		let bag = bag_from(&[("red", 12), ("green", 13), ("blue", 14)]);
+		assert_eq!(8, sum_possible_ids(&games, &bag));
 	}
 
 	#[test]
 	fn test_sample_2() {
-		let games = [
+		let games: Vec<String> = [
 			"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
 			"Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
 			"Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
@@ -154,9 +226,11 @@ mod tests {
 		.map(|&s| s.to_string())
 		.collect();
 
-		let min_pieces = get_min_pieces(&games);
-		let power_sum = min_pieces.iter().fold(0, |sum, &(x, y, z)| sum + x * y * z);
+		let games = parse_games(&games).unwrap();
+		let colors = ["red", "green", "blue"];
+		assert_eq!(games[0].minimum_bag(&colors), bag_from(&[("red", 4), ("green", 2), ("blue", 6)]));
+		assert_eq!(games[0].power(&colors), 48);
 
-		assert_eq!(2286, power_sum);
+		assert_eq!(2286, sum_powers(&games, &colors));
 	}
 }
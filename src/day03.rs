@@ -1,125 +1,189 @@
-use aoc_2023::utils;
-use aoc_2023::utils::Point;
+use aoc_2023::utils::utils;
+use aoc_2023::utils::grid::Grid;
+use aoc_2023::utils::point::Point;
+use aoc_2023::utils::rect::Rect;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 struct Part {
 	number: u32,
-	position: Point
+	position: Point<usize>,
+	// Digits as written in the schematic, leading zeros included
+	length: usize
 }
 
 impl Part {
-	fn is_adjacent(&self, position: Point) -> bool {
-		let length = self.number.ilog10();
-		let horizontal = self.position.y.checked_sub(1).unwrap_or(0) ..= self.position.y + length + 1;
-		let vertical = self.position.x.checked_sub(1).unwrap_or(0) ..= self.position.x + 1;
+	fn bounds(&self) -> Rect<usize> {
+		Rect::new(self.position, self.position + Point::new(0, self.length - 1))
+	}
 
-		horizontal.contains(&position.y) && vertical.contains(&position.x)
+	fn is_adjacent(&self, position: Point<usize>) -> bool {
+		self.bounds().expand(1).contains(&position)
 	}
 }
 
-fn is_valid_symbol(symbol: char, is_gear: bool) -> bool {
-	(is_gear && symbol == '*') || symbol != '.' && symbol.is_ascii_punctuation()
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Symbol {
+	value: char,
+	position: Point<usize>
 }
 
-fn extract_points(engine: &Vec<String>, is_gear: bool) -> Vec<Point> {
-	let mut points = Vec::new();
-	let mut i = 0;
+// Gear ratios only need `Exactly` and `Product`, the remaining query variants are only
+// used by the tests, hence the dead-code allowance on both enums
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(not(test), allow(dead_code))]
+enum AdjacentCount {
+	Exactly(usize),
+	AtLeast(usize)
+}
 
-	for row in engine {
-		let mut j = 0;
+impl AdjacentCount {
+	fn matches(&self, count: usize) -> bool {
+		match *self {
+			Self::Exactly(expected) => count == expected,
+			Self::AtLeast(minimum) => count >= minimum
+		}
+	}
+}
 
-		for c in row.chars() {
-			if is_valid_symbol(c, is_gear) {
-				points.push(Point { x: i, y: j });
-			}
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(not(test), allow(dead_code))]
+enum Aggregate {
+	Product,
+	Sum,
+	Max
+}
 
-			j += 1;
+impl Aggregate {
+	fn apply<I>(&self, numbers: I) -> u32 where I: Iterator<Item = u32> {
+		match self {
+			Self::Product => numbers.product(),
+			Self::Sum => numbers.sum(),
+			Self::Max => numbers.max().unwrap_or(0)
 		}
-
-		i += 1;
 	}
+}
 
-	points
+#[derive(Debug)]
+struct Schematic {
+	parts: Vec<Part>,
+	symbols: Vec<Symbol>,
+	// For each symbol (same order as `symbols`), indices into `parts` adjacent to it
+	adjacent_parts: Vec<Vec<usize>>
 }
 
-fn get_numbers(input: &Vec<String>) -> Vec<Part> {
-	input.iter().enumerate().flat_map(|(row_index, row)| {
-		let mut column_index = 0;
+impl Schematic {
+	fn from_grid(grid: &Grid) -> Self {
+		Self::from_grid_with(grid, is_valid_symbol)
+	}
+
+	fn from_grid_with<F>(grid: &Grid, is_symbol: F) -> Self where F: Fn(char) -> bool {
+		let (columns, rows) = grid.get_size();
+		let mut parts = Vec::new();
+		let mut symbols = Vec::new();
+		// Which part, if any, covers each cell of the grid
+		let mut owners: Vec<Option<usize>> = vec![None; rows * columns];
 
-		row.chars().enumerate().flat_map(move |(char_index, c)| {
-			if char_index < column_index {
-				return None
-			}
+		for (x, row) in grid.rows().enumerate() {
+			let mut y = 0;
 
-			if let Some(digit) = c.to_digit(10) {
-				let mut number = String::new();
-				number.push(c);
+			while y < columns {
+				let value = row[y];
 
-				let mut next_char_index = char_index + 1;
+				if value.is_ascii_digit() {
+					let start = y;
+					let mut number = 0;
 
-				while let Some(next_char) = row.chars().nth(next_char_index) {
-					if let Some(next_digit) = next_char.to_digit(10) {
-						number.push(next_char);
-						next_char_index += 1;
-					} else {
-						break;
+					while y < columns && row[y].is_ascii_digit() {
+						number = number * 10 + row[y].to_digit(10).unwrap();
+						owners[x * columns + y] = Some(parts.len());
+						y += 1;
 					}
+
+					parts.push(Part { number, position: Point::new(x, start), length: y - start });
+					continue;
 				}
 
-				column_index = next_char_index;
+				if is_symbol(value) {
+					symbols.push(Symbol { value, position: Point::new(x, y) });
+				}
 
-				Some(Part {
-					number: number.parse::<u32>().unwrap(),
-					position: Point { x: row_index as u32, y: char_index as u32 }
-				})
-			} else {
-				column_index += 1;
-				None
+				y += 1;
 			}
-		})
-	}).collect()
-}
+		}
+
+		let adjacent_parts = symbols
+			.iter()
+			.map(|symbol| {
+				let mut adjacent: Vec<usize> = Vec::new();
 
-fn sum_parts(schema: &Vec<String>, is_gear: bool) -> u32 {
-	let points = extract_points(&schema, is_gear);
-	let parts = get_numbers(&schema);
+				let Point { x, y } = symbol.position;
 
-	if !is_gear {
-		parts.iter().filter(|&part| {
-			points.iter().any(|&point| {
-				part.is_adjacent(point)
+				for neighbour_x in x.saturating_sub(1) ..= (x + 1).min(rows - 1) {
+					for neighbour_y in y.saturating_sub(1) ..= (y + 1).min(columns - 1) {
+						if let Some(part) = owners[neighbour_x * columns + neighbour_y] {
+							if !adjacent.contains(&part) {
+								adjacent.push(part);
+							}
+						}
+					}
+				}
+
+				debug_assert!(adjacent.iter().all(|&part| parts[part].is_adjacent(symbol.position)));
+				adjacent
 			})
-		}).map(|&part| part.number)
+			.collect();
+
+		Self { parts, symbols, adjacent_parts }
+	}
+
+	fn sum_part_numbers(&self) -> u32 {
+		let mut is_part = vec![false; self.parts.len()];
+
+		self.adjacent_parts
+			.iter()
+			.flatten()
+			.for_each(|&part| is_part[part] = true);
+
+		self.parts
+			.iter()
+			.zip(is_part)
+			.filter(|&(_, is_part)| is_part)
+			.map(|(part, _)| part.number)
 			.sum()
-	} else {
-		points.iter().filter_map(|&point| {
-			let adjacent_parts: Vec<u32> = parts
-				.iter()
-				.filter(|part| part.is_adjacent(point))
-				.map(|part| part.number)
-				.collect();
-
-			if adjacent_parts.len() == 2 {
-				Some(adjacent_parts[0] * adjacent_parts[1])
-			} else {
-				None
-			}
-		}).sum()
 	}
+
+	// Aggregated adjacent numbers for every symbol of the given kind (any symbol when `None`)
+	// whose amount of adjacent numbers satisfies `count`
+	fn query(&self, kind: Option<char>, count: AdjacentCount, aggregate: Aggregate) -> impl Iterator<Item = u32> + '_ {
+		self.symbols
+			.iter()
+			.zip(&self.adjacent_parts)
+			.filter(move |(symbol, adjacent)| {
+				kind.is_none_or(|kind| symbol.value == kind) && count.matches(adjacent.len())
+			})
+			.map(move |(_, adjacent)| aggregate.apply(adjacent.iter().map(|&part| self.parts[part].number)))
+	}
+
+	fn sum_gear_ratios(&self) -> u32 {
+		self.query(Some('*'), AdjacentCount::Exactly(2), Aggregate::Product).sum()
+	}
+}
+
+fn is_valid_symbol(symbol: char) -> bool {
+	symbol != '.' && symbol.is_ascii_punctuation()
 }
 
 fn main() {
-	let schema = utils::read_file_to_vector("input/2023/day3.txt").unwrap();
-	let sum = sum_parts(&schema, false);
-	println!("{}", sum);
+	let input = utils::read_file_to_vector("input/2023/day3.txt").unwrap();
+	let schematic = Schematic::from_grid(&Grid::from_string_vec(&input));
 
-	let sum_gears = sum_parts(&schema, true);
-	println!("{}", sum_gears);
+	println!("{}", schematic.sum_part_numbers());
+	println!("{}", schematic.sum_gear_ratios());
 }
 
 #[cfg(test)]
 mod tests {
-	use aoc_2023::utils::StringExtension;
+	use aoc_2023::utils::utils::StringExtension;
 	use super::*;
 
 	const SAMPLE: &str = "\
@@ -134,21 +198,23 @@ mod tests {
 ...$.*....
 .664.598..";
 
+	fn sample_schematic() -> Schematic {
+		Schematic::from_grid(&Grid::from_string_vec(&SAMPLE.to_string_vector()))
+	}
+
 	#[test]
 	fn test_valid_symbols() {
-		assert_eq!(is_valid_symbol('_', false), true);
-		assert_eq!(is_valid_symbol('+', false), true);
-		assert_eq!(is_valid_symbol('*', false), true);
-		assert_eq!(is_valid_symbol('@', false), true);
-		assert_eq!(is_valid_symbol('.', false), false);
-		assert_eq!(is_valid_symbol('d', false), false);
-		assert_eq!(is_valid_symbol('8', false), false);
+		assert_eq!(is_valid_symbol('_'), true);
+		assert_eq!(is_valid_symbol('+'), true);
+		assert_eq!(is_valid_symbol('*'), true);
+		assert_eq!(is_valid_symbol('@'), true);
+		assert_eq!(is_valid_symbol('.'), false);
+		assert_eq!(is_valid_symbol('d'), false);
+		assert_eq!(is_valid_symbol('8'), false);
 	}
 
 	#[test]
 	fn test_extract_symbol_coordinate() {
-		let sample: Vec<String> = SAMPLE.to_string_vector();
-
 		let points = vec![
 			Point { x: 1, y: 3 },
 			Point { x: 3, y: 6 },
@@ -158,25 +224,55 @@ mod tests {
 			Point { x: 8, y: 5 }
 		];
 
-		let extracted_points = extract_points(&sample, false);
+		let extracted_points: Vec<Point<usize>> = sample_schematic().symbols.iter().map(|symbol| symbol.position).collect();
 		assert_eq!(extracted_points.len(), points.len());
 		assert_eq!(points, extracted_points);
 	}
 
 	#[test]
 	fn test_extract_numbers() {
-		let sample: Vec<String> = SAMPLE.to_string_vector();
+		let numbers: Vec<u32> = sample_schematic().parts.iter().map(|part| part.number).collect();
+
+		assert_eq!(numbers, vec![467, 114, 35, 633, 617, 58, 592, 755, 664, 598]);
+	}
+
+	#[test]
+	fn test_symbol_adjacency() {
+		let schematic = sample_schematic();
+		let adjacent: Vec<u32> = schematic.adjacent_parts[0].iter().map(|&part| schematic.parts[part].number).collect();
+
+		assert_eq!(adjacent, vec![467, 35]);
+	}
+
+	#[test]
+	fn test_query() {
+		let schematic = sample_schematic();
+
+		let at_least_one: Vec<u32> = schematic.query(None, AdjacentCount::AtLeast(1), Aggregate::Sum).collect();
+		assert_eq!(at_least_one, vec![467 + 35, 633, 617, 592, 664, 755 + 598]);
 
-		let numbers = get_numbers(&sample);
+		let single_stars: Vec<u32> = schematic.query(Some('*'), AdjacentCount::Exactly(1), Aggregate::Max).collect();
+		assert_eq!(single_stars, vec![617]);
 
-		println!("{:?}", numbers);
+		let largest_gear = schematic.query(Some('*'), AdjacentCount::Exactly(2), Aggregate::Product).max();
+		assert_eq!(largest_gear, Some(755 * 598));
+	}
+
+	#[test]
+	fn test_custom_classifier() {
+		let grid = Grid::from_string_vec(&SAMPLE.to_string_vector());
+		let schematic = Schematic::from_grid_with(&grid, |symbol| symbol == '$' || symbol == '#');
+
+		assert_eq!(schematic.symbols.len(), 2);
+		assert_eq!(schematic.sum_part_numbers(), 633 + 664);
 	}
 
 	#[test]
 	fn test_adjacent() {
 		let part = Part {
 			number: 457,
-			position: Point { x: 2, y: 5 }};
+			position: Point { x: 2, y: 5 },
+			length: 3 };
 
 		assert_eq!(part.is_adjacent(Point { x: 2, y: 6}), true);
 		assert_eq!(part.is_adjacent(Point { x: 1, y: 3}), false);
@@ -185,18 +281,26 @@ mod tests {
 	}
 
 	#[test]
-	fn test_sample_1() {
-		let sample: Vec<String> = SAMPLE.to_string_vector();
-		let sum = sum_parts(&sample, false);
+	fn test_part_bounds() {
+		let part = Part { number: 457, position: Point::new(0, 0), length: 3 };
+		let bounds = part.bounds().expand(1);
 
-		assert_eq!(sum, 4361)
+		assert_eq!(part.bounds(), Rect::new(Point::new(0, 0), Point::new(0, 2)));
+		assert_eq!(bounds, Rect::new(Point::new(0, 0), Point::new(1, 3)));
+		assert_eq!(bounds.area(), 8);
+
+		let padded = Schematic::from_grid(&Grid::parse("007.\n...$").unwrap());
+		assert_eq!(padded.parts[0].bounds(), Rect::new(Point::new(0, 0), Point::new(0, 2)));
+		assert_eq!(padded.sum_part_numbers(), 7);
 	}
 
 	#[test]
-	fn test_sample_2() {
-		let sample: Vec<String> = SAMPLE.to_string_vector();
-		let sum = sum_parts(&sample, true);
+	fn test_sample_1() {
+		assert_eq!(sample_schematic().sum_part_numbers(), 4361)
+	}
 
-		assert_eq!(sum, 467835)
+	#[test]
+	fn test_sample_2() {
+		assert_eq!(sample_schematic().sum_gear_ratios(), 467835)
 	}
-}
\ No newline at end of file
+}
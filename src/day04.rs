@@ -1,55 +1,148 @@
-use aoc_2023::utils;
+use core::fmt;
+use std::collections::HashSet;
 
-fn union_elements_count(lhs: Vec<&str>, rhs: Vec<&str>) -> usize {
-	lhs.iter()
-		.filter(|&x| rhs.contains(x))
+use aoc_2023::utils::utils;
+
+#[derive(Debug, PartialEq)]
+enum CardError {
+	Malformed(String),
+	DuplicateNumber { card: u32, number: u32 }
+}
+
+fn intersection_count(lhs: &HashSet<u32>, rhs: &HashSet<u32>) -> usize {
+	rhs.iter()
+		.filter(|&x| lhs.contains(x))
 		.count()
 }
 
-fn get_card_score(card: &String) -> (u32, usize) {
-	let numbers: Vec<_> = card.split(": ").skip(1).nth(0).unwrap().split(" | ").collect();
-	let lottery: Vec<_> = numbers[0].split_whitespace().collect();
-	let hold: Vec<_> = numbers[1].split_whitespace().collect();
-	let matched_count = union_elements_count(lottery, hold);
+fn parse_numbers(card: u32, numbers: &str) -> Result<HashSet<u32>, CardError> {
+	let mut set = HashSet::new();
+
+	for token in numbers.split_whitespace() {
+		let number = token.parse::<u32>().map_err(|_| CardError::Malformed(token.to_string()))?;
+
+		if !set.insert(number) {
+			return Err(CardError::DuplicateNumber { card, number });
+		}
+	}
 
-	(if matched_count == 0 { 0 } else { u32::pow(2, (matched_count - 1) as u32) }, matched_count)
+	Ok(set)
 }
 
-fn sum_cards_score(cards: &Vec<String>) -> (u32, u32) {
-	let mut part1_sum = 0;
-	let mut part2_sum = 0;
+#[derive(Debug, Clone, PartialEq)]
+struct Scratchcard {
+	id: u32,
+	winning: HashSet<u32>,
+	numbers: HashSet<u32>
+}
 
-	let mut card_index = 0;
-	let mut card_copies: Vec<u32> = vec![1; cards.len()];
+impl Scratchcard {
+	fn parse(card: &str) -> Result<Self, CardError> {
+		let malformed = || CardError::Malformed(card.to_string());
+
+		let (header, numbers) = card.split_once(": ").ok_or_else(malformed)?;
+		let (winning, numbers) = numbers.split_once(" | ").ok_or_else(malformed)?;
+		let id = header
+			.strip_prefix("Card")
+			.and_then(|id| id.trim().parse().ok())
+			.ok_or_else(malformed)?;
+
+		Ok(Self {
+			id,
+			winning: parse_numbers(id, winning)?,
+			numbers: parse_numbers(id, numbers)?
+		})
+	}
 
-	for card in cards {
-		let (score, match_count) = get_card_score(card);
+	fn matches(&self) -> usize {
+		intersection_count(&self.winning, &self.numbers)
+	}
 
-		part1_sum += score;
-		part2_sum += card_copies[card_index];
+	fn score(&self) -> u32 {
+		match self.matches() {
+			0 => 0,
+			count => u32::pow(2, (count - 1) as u32)
+		}
+	}
+}
 
-		card_index += 1;
+// A single step of the cascade: every copy of `source` won one copy of `target`
+#[derive(Debug, Clone, PartialEq)]
+struct CopyEvent {
+	source: u32,
+	target: u32,
+	copies: u32
+}
+
+impl fmt::Display for CopyEvent {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} -> {} x{}", self.source, self.target, self.copies)
+	}
+}
+
+#[derive(Debug)]
+struct Cascade {
+	copies: Vec<u32>,
+	trace: Vec<CopyEvent>
+}
 
-		for i in card_index..card_index+match_count {
-			card_copies[i] += card_copies[card_index - 1];
+impl Cascade {
+	fn run(cards: &[Scratchcard]) -> Self {
+		let mut copies: Vec<u32> = vec![1; cards.len()];
+		let mut trace = Vec::new();
+
+		for (index, card) in cards.iter().enumerate() {
+			// Cards never make you copy a card past the end of the table
+			let last = (index + card.matches()).min(cards.len() - 1);
+
+			for target in index + 1 ..= last {
+				copies[target] += copies[index];
+
+				trace.push(CopyEvent {
+					source: card.id,
+					target: cards[target].id,
+					copies: copies[index]
+				});
+			}
 		}
+
+		Self { copies, trace }
+	}
+
+	fn total(&self) -> u32 {
+		self.copies.iter().sum()
 	}
+}
+
+fn parse_cards(cards: &[String]) -> Result<Vec<Scratchcard>, CardError> {
+	cards.iter().map(|card| Scratchcard::parse(card)).collect()
+}
 
-	(part1_sum, part2_sum)
+fn sum_cards_score(cards: &[Scratchcard]) -> u32 {
+	cards.iter().map(|card| card.score()).sum()
 }
 
 fn main() {
-	let cards = utils::read_file_to_vector("input/2023/day4.txt").unwrap();
-	let (part1_sum, part2_sum) = sum_cards_score(&cards);
+	let input = utils::read_file_to_vector("input/2023/day4.txt").unwrap();
+	let cards = parse_cards(&input).unwrap();
+
+	let cascade = Cascade::run(&cards);
 
-	println!("{}", part1_sum);
-	println!("{}", part2_sum)
+	println!("{}", sum_cards_score(&cards));
+	println!("{}", cascade.total());
+
+	// Every copy won along the way is only listed when asked for with `--trace`
+	if std::env::args().any(|argument| argument == "--trace") {
+		for event in &cascade.trace {
+			println!("{}", event);
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use aoc_2023::utils::StringExtension;
+	use aoc_2023::utils::utils::StringExtension;
 
 	const SAMPLE: &str = "\
 Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
@@ -63,35 +156,81 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
 	#[test]
 	fn test_matching_numbers() {
-		let card = SAMPLE_CARD.to_string();
-		let numbers: Vec<_> = card.split(": ").skip(1).nth(0).unwrap().split(" | ").collect();
-		let lottery: Vec<_> = numbers[0].split_whitespace().collect();
-		let hold: Vec<_> = numbers[1].split_whitespace().collect();
-		let matched_count = union_elements_count(lottery, hold);
+		let card = Scratchcard::parse(SAMPLE_CARD).unwrap();
+		let matched_count = intersection_count(&card.winning, &card.numbers);
 
 		assert_eq!(matched_count, 4);
 	}
 
+	#[test]
+	fn test_leading_zeros_match() {
+		let card = Scratchcard::parse("Card 1: 06 17 | 6 017 5").unwrap();
+
+		assert_eq!(card.matches(), 2);
+	}
+
+	#[test]
+	fn test_duplicate_numbers() {
+		assert_eq!(
+			Scratchcard::parse("Card 3: 41 48 41 | 83 86"),
+			Err(CardError::DuplicateNumber { card: 3, number: 41 }));
+
+		assert_eq!(
+			Scratchcard::parse("Card 4: 41 48 | 83 086 86"),
+			Err(CardError::DuplicateNumber { card: 4, number: 86 }));
+	}
+
+	#[test]
+	fn test_malformed_card() {
+		assert_eq!(
+			Scratchcard::parse("Card 1: 41 4x | 83"),
+			Err(CardError::Malformed("4x".to_string())));
+
+		assert!(Scratchcard::parse("Card 1 41 48 83").is_err());
+	}
+
 	#[test]
 	fn test_single_card_score() {
-		let score = get_card_score(&SAMPLE_CARD.to_string()).0;
+		let score = Scratchcard::parse(SAMPLE_CARD).unwrap().score();
 
 		assert_eq!(score, 8)
 	}
 
 	#[test]
 	fn test_sample_1() {
-		let cards = SAMPLE.to_string_vector();
-		let sum = sum_cards_score(&cards).0;
+		let cards = parse_cards(&SAMPLE.to_string_vector()).unwrap();
+		let sum = sum_cards_score(&cards);
 
 		assert_eq!(sum, 13);
 	}
 
 	#[test]
 	fn test_sample_2() {
-		let cards = SAMPLE.to_string_vector();
-		let sum = sum_cards_score(&cards).1;
+		let cards = parse_cards(&SAMPLE.to_string_vector()).unwrap();
+		let cascade = Cascade::run(&cards);
+
+		assert_eq!(cascade.copies, vec![1, 2, 4, 8, 14, 1]);
+		assert_eq!(cascade.total(), 30);
+	}
+
+	#[test]
+	fn test_cascade_trace() {
+		let cards = parse_cards(&SAMPLE.to_string_vector()).unwrap();
+		let trace = Cascade::run(&cards).trace;
 
-		assert_eq!(sum, 30);
+		assert_eq!(trace[0], CopyEvent { source: 1, target: 2, copies: 1 });
+		assert_eq!(trace[0].to_string(), "1 -> 2 x1");
+		assert_eq!(trace.iter().filter(|event| event.source == 4).collect::<Vec<_>>(), vec![&CopyEvent { source: 4, target: 5, copies: 8 }]);
+	}
+
+	#[test]
+	fn test_cascade_clamps_at_end() {
+		let input = "\
+Card 1: 1 2 3 | 1 2 3
+Card 2: 4 5 6 | 4 5 6".to_string_vector();
+		let cascade = Cascade::run(&parse_cards(&input).unwrap());
+
+		assert_eq!(cascade.copies, vec![1, 2]);
+		assert_eq!(cascade.trace.len(), 1);
 	}
 }
@@ -1,151 +1,325 @@
-use std::collections::HashMap;
+use std::collections::VecDeque;
 
 use aoc_2023::utils::utils;
-use aoc_2023::utils::{point::*, grid::*};
-
-
-fn get_first_directions(start: Point, grid: &Grid) -> Vec<Direction> {
-	let map = HashMap::from([
-		(Direction::South, "|LJ"),
-		(Direction::North, "|FJ"),
-		(Direction::West, "-LF"),
-		(Direction::East, "-7J"),
-	]);
-
-	// let mut directions = vec![];
-
-	// for direction in [Direction::North, Direction::East, Direction::South, Direction::West] {
-	// 	let new_point = start + direction.point();
-
-	// 	if let Some(value) = grid.get_value(new_point.x as usize, new_point.y as usize) {
-	// 		let is_valid = map.get(&direction).unwrap().contains(value);
-
-	// 		if is_valid {
-	// 			directions.push(direction);
-	// 		}
-	// 	}
-	// }
-
-	// directions
-
-	[Direction::North, Direction::East, Direction::South, Direction::West]
-		.iter()
-		.filter_map(|direction| {
-			let new_point = start + direction.point();
-
-			grid.get_value(new_point.x as usize, new_point.y as usize)
-				.and_then(|value| {
-					map.get(&direction).map(|&valid_chars| valid_chars.contains(value))
-	 			})
-				.map(|is_valid| if is_valid { Some(direction) } else { None })
-				.flatten()
-		})
-		.cloned()
-		.collect::<Vec<Direction>>()
+use aoc_2023::utils::{point::*, grid::*, render::*, bit_grid::BitGrid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+	Vertical,
+	Horizontal,
+	NorthEast,
+	NorthWest,
+	SouthWest,
+	SouthEast,
+	Ground,
+	Start
 }
 
-fn record_trip(value: char, log_grid: &mut Grid, position: &Point) {
-	// Use map for nice print on console :D
-	let map: HashMap<char, char> = HashMap::from([
-		('L', '└'),
-		('J', '┘'),
-		('7', '┐'),
-		('F', '┌'),
-		('|', '│'),
-		('-', '─')
-	]);
-
-	log_grid.set_value(value, position.x as usize, position.y as usize);
-}
+impl Tile {
+	fn from(value: char) -> Option<Self> {
+		match value {
+			'|' => Some(Self::Vertical),
+			'-' => Some(Self::Horizontal),
+			'L' => Some(Self::NorthEast),
+			'J' => Some(Self::NorthWest),
+			'7' => Some(Self::SouthWest),
+			'F' => Some(Self::SouthEast),
+			'.' => Some(Self::Ground),
+			'S' => Some(Self::Start),
+			_ => None
+		}
+	}
+
+	fn from_connections(lhs: &Direction, rhs: &Direction) -> Option<Self> {
+		[Self::Vertical, Self::Horizontal, Self::NorthEast, Self::NorthWest, Self::SouthWest, Self::SouthEast]
+			.into_iter()
+			.find(|tile| lhs != rhs && tile.connects(lhs) && tile.connects(rhs))
+	}
+
+	fn to_char(self) -> char {
+		match self {
+			Self::Vertical => '|',
+			Self::Horizontal => '-',
+			Self::NorthEast => 'L',
+			Self::NorthWest => 'J',
+			Self::SouthWest => '7',
+			Self::SouthEast => 'F',
+			Self::Ground => '.',
+			Self::Start => 'S'
+		}
+	}
+
+	// Sides of the tile a pipe leaves through. Ground and an uninferred start connect nowhere
+	fn connections(&self) -> Vec<Direction> {
+		match self {
+			Self::Vertical => vec![Direction::North, Direction::South],
+			Self::Horizontal => vec![Direction::East, Direction::West],
+			Self::NorthEast => vec![Direction::North, Direction::East],
+			Self::NorthWest => vec![Direction::North, Direction::West],
+			Self::SouthWest => vec![Direction::South, Direction::West],
+			Self::SouthEast => vec![Direction::South, Direction::East],
+			Self::Ground | Self::Start => vec![]
+		}
+	}
+
+	fn connects(&self, direction: &Direction) -> bool {
+		self.connections().contains(direction)
+	}
 
-fn get_char_for_directions(lhs: Direction, rhs: Direction) -> char {
-	match (lhs, rhs) {
-		(Direction::North, Direction::South) | (Direction::South, Direction::North) => '|',
-		(Direction::North, Direction::East) | (Direction::East, Direction::North) => 'L',
-		(Direction::North, Direction::West) | (Direction::West, Direction::North) => 'J',
-		(Direction::South, Direction::East) | (Direction::East, Direction::South) => 'F',
-		(Direction::South, Direction::West) | (Direction::West, Direction::South) => '7',
-		(Direction::East, Direction::West) | (Direction::West, Direction::East) => '-',
-		_ => panic!("Wrong direction set")
+	// Direction to leave the tile when entering it travelling `direction`
+	fn exit(&self, direction: &Direction) -> Option<Direction> {
+		let entry = direction.opposite();
+
+		if !self.connects(&entry) {
+			return None
+		}
+
+		self.connections().into_iter().find(|exit| *exit != entry)
 	}
 }
 
-fn count_inner_tiles(log_grid: &mut Grid) -> i32 {
-	let mut within_loop = false;
-	let mut tiles_loop = 0;
-
-	for i in 0 .. log_grid.get_size().1 {
-		for j in 0 .. log_grid.get_size().0 {
-			let value = log_grid.get_value(i, j).unwrap();
-
-			match value {
-				 '7' | 'F' | '|' => within_loop = !within_loop,
-				 'L' | 'J' |'-' => continue,
-				'.' => if within_loop {
-					tiles_loop += 1;
-					log_grid.set_value('I', i, j);
-				} else {
-					log_grid.set_value('O', i, j);
-				},
-				_ => panic!("Oops")
+#[derive(Debug, PartialEq)]
+enum PipeError {
+	UnknownTile { value: char, position: Point },
+	MissingStart,
+	MultipleStarts { positions: Vec<Point> },
+	// The loop can't continue: the pipe leads off the map or into a tile not connecting back
+	DeadEnd { position: Point },
+	// More than two pipes connect to the start, so the loop is ambiguous
+	Branching { position: Point, connections: usize }
+}
+
+// The solution uses ray casting, the other strategies are cross-checked against it in the tests
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(test), allow(dead_code))]
+enum Strategy {
+	// Flip a flag on every vertical wall crossed along each row
+	RayCasting,
+	// Upscale each tile to 3x3, flood the outside from the border and downsample
+	FloodFill,
+	// Area enclosed by the loop (Shoelace formula) and Pick's theorem for the interior points
+	Shoelace
+}
+
+#[derive(Debug)]
+struct PipeNetwork {
+	tiles: Vec<Tile>,
+	rows: usize,
+	columns: usize,
+	start: Point,
+	start_tile: Tile,
+	main_loop: Vec<Point>
+}
+
+impl PipeNetwork {
+	fn parse(grid: &Grid) -> Result<Self, PipeError> {
+		let (columns, rows) = grid.get_size();
+		let mut tiles = Vec::with_capacity(rows * columns);
+
+		for (x, row) in grid.rows().enumerate() {
+			for (y, &value) in row.iter().enumerate() {
+				let tile = Tile::from(value)
+					.ok_or(PipeError::UnknownTile { value, position: Point::new(x as i64, y as i64) })?;
+
+				tiles.push(tile);
 			}
 		}
+
+		let starts: Vec<Point> = grid
+			.find_all('S')
+			.iter()
+			.map(|&(x, y)| Point::new(x as i64, y as i64))
+			.collect();
+
+		let start = match starts.as_slice() {
+			[] => return Err(PipeError::MissingStart),
+			[start] => *start,
+			_ => return Err(PipeError::MultipleStarts { positions: starts })
+		};
+
+		let mut network = Self {
+			tiles,
+			rows,
+			columns,
+			start,
+			start_tile: Tile::Start,
+			main_loop: vec![]
+		};
+
+		network.start_tile = network.infer_start_tile()?;
+		network.main_loop = network.trace_loop()?;
+
+		Ok(network)
 	}
 
-	tiles_loop
-}
+	fn get_tile(&self, position: &Point) -> Option<Tile> {
+		if position.x < 0 || position.y < 0 || position.x >= self.rows as i64 || position.y >= self.columns as i64 {
+			return None
+		}
+
+		let tile = self.tiles[position.x as usize * self.columns + position.y as usize];
 
-fn get_next_direction(value: char, current_direction: Direction) -> Direction {
-	match value {
-		 'L' => if current_direction == Direction::South { Direction::East } else { Direction::North },
-		 'J' => if current_direction == Direction::East { Direction::North } else { Direction::West },
-		 '7' => if current_direction == Direction::East { Direction::South } else { Direction::West },
-		 'F' => if current_direction == Direction::North { Direction::East } else { Direction::South },
-		 '|' | '-' => current_direction,
-		 _ => panic!("Oops"),
+		if tile == Tile::Start { Some(self.start_tile) } else { Some(tile) }
 	}
-}
 
-fn traverse_pipes(start: Point, grid: &Grid) -> (i32, i32) {
-	let mut log_grid = grid.empty_copy_with_default('.');
-	log_grid.set_value('S', start.x as usize, start.y as usize);
+	fn infer_start_tile(&self) -> Result<Tile, PipeError> {
+		let connected: Vec<Direction> = Direction::ALL
+			.iter()
+			.filter(|&direction| {
+				self.get_tile(&(self.start + direction.point()))
+					.is_some_and(|tile| tile.connects(&direction.opposite()))
+			})
+			.copied()
+			.collect();
+
+		match connected.as_slice() {
+			[lhs, rhs] => Ok(Tile::from_connections(lhs, rhs).unwrap()),
+			[] | [_] => Err(PipeError::DeadEnd { position: self.start }),
+			_ => Err(PipeError::Branching { position: self.start, connections: connected.len() })
+		}
+	}
+
+	fn trace_loop(&self) -> Result<Vec<Point>, PipeError> {
+		let mut main_loop = vec![self.start];
+		let mut current_location = self.start;
+		let mut direction = self.start_tile.connections()[0];
+
+		loop {
+			let previous_location = current_location;
+			current_location.move_to(&direction);
+
+			if current_location == self.start {
+				return Ok(main_loop)
+			}
+
+			direction = self.get_tile(&current_location)
+				.and_then(|tile| tile.exit(&direction))
+				.ok_or(PipeError::DeadEnd { position: previous_location })?;
+
+			main_loop.push(current_location);
+		}
+	}
+
+	fn farthest_distance(&self) -> usize {
+		self.main_loop.len() / 2
+	}
+
+	fn loop_mask(&self) -> BitGrid {
+		let mut mask = BitGrid::new(self.rows, self.columns);
+
+		for position in &self.main_loop {
+			mask.set(position.x as usize, position.y as usize, true);
+		}
+
+		mask
+	}
 
-	let first_directions = get_first_directions(start, &grid);
+	// Grid with only the main loop pipes (start already replaced) and every other tile
+	// marked as inside (`I`) or outside (`O`) of the loop
+	fn classify(&self) -> Grid {
+		let mask = self.loop_mask();
+		let mut log_grid = Grid::new(self.rows, self.columns, '.');
 
-	let mut current_location = start.clone();
-	let mut direction = first_directions[0].clone();
-	current_location.move_to(&direction);
+		for x in 0 .. self.rows {
+			let mut within_loop = false;
 
-	let mut distance = 1;
+			for y in 0 .. self.columns {
+				let tile = self.get_tile(&Point::new(x as i64, y as i64)).unwrap();
 
-	while start != current_location {
-		let value = grid.get_value(current_location.x as usize, current_location.y as usize).unwrap();
-		record_trip(value, &mut log_grid, &current_location);
+				let value = if mask.get(x, y) {
+					if tile.connects(&Direction::North) {
+						within_loop = !within_loop;
+					}
 
-		direction = get_next_direction(value, direction);
-		current_location.move_to(&direction);
+					tile.to_char()
+				} else if within_loop { 'I' } else { 'O' };
 
-		distance += 1;
+				log_grid.set_value(value, x, y);
+			}
+		}
+
+		log_grid
 	}
 
-	record_trip(
-		get_char_for_directions(first_directions[0].clone(), first_directions[1].clone()),
-		&mut log_grid,
-		&current_location);
+	// Every tile becomes a 3x3 block with walls along the pipe, so the flood can squeeze
+	// between parallel pipes. Tiles whose block centre is not reached are inside
+	fn count_flood_fill(&self) -> usize {
+		let (rows, columns) = (self.rows * 3, self.columns * 3);
+		let mut walls = vec![false; rows * columns];
 
-	let tiles_loop = count_inner_tiles(&mut log_grid);
+		for position in &self.main_loop {
+			let tile = self.get_tile(position).unwrap();
+			let centre = Point::new(position.x * 3 + 1, position.y * 3 + 1);
 
-	(distance / 2, tiles_loop)
+			walls[centre.x as usize * columns + centre.y as usize] = true;
+
+			for direction in tile.connections() {
+				let arm = centre + direction.point();
+				walls[arm.x as usize * columns + arm.y as usize] = true;
+			}
+		}
+
+		let mut outside = vec![false; rows * columns];
+		let mut queue = VecDeque::from([Point::new(0, 0)]);
+		outside[0] = true;
+
+		while let Some(current) = queue.pop_front() {
+			for direction in Direction::ALL.iter() {
+				let next = current + direction.point();
+
+				if next.x < 0 || next.y < 0 || next.x >= rows as i64 || next.y >= columns as i64 {
+					continue;
+				}
+
+				let index = next.x as usize * columns + next.y as usize;
+
+				if !walls[index] && !outside[index] {
+					outside[index] = true;
+					queue.push_back(next);
+				}
+			}
+		}
+
+		let mask = self.loop_mask();
+
+		(0 .. self.rows)
+			.flat_map(|x| (0 .. self.columns).map(move |y| (x, y)))
+			.filter(|&(x, y)| !mask.get(x, y) && !outside[(x * 3 + 1) * columns + y * 3 + 1])
+			.count()
+	}
+
+	fn count_shoelace(&self) -> usize {
+		let double_area: i64 = self.main_loop
+			.iter()
+			.zip(self.main_loop.iter().cycle().skip(1))
+			.map(|(current, next)| current.x * next.y - next.x * current.y)
+			.sum();
+
+		let area = double_area.unsigned_abs() as usize / 2;
+
+		area + 1 - self.main_loop.len() / 2
+	}
+
+	fn count_inner_tiles(&self, strategy: Strategy) -> usize {
+		match strategy {
+			Strategy::RayCasting => self.classify().find_all('I').len(),
+			Strategy::FloodFill => self.count_flood_fill(),
+			Strategy::Shoelace => self.count_shoelace()
+		}
+	}
 }
 
 fn main() {
 	let input = utils::read_file_to_vector("input/2023/day10.txt").unwrap();
-	let grid = Grid::from_string_vec(&input);
-	let start = Point::from(grid.find('S').unwrap());
-	let result = traverse_pipes(start, &grid);
+	let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
 
-	println!("Distance: {}", result.0);
-	println!("Tiles: {}", result.1);
+	println!("Distance: {}", network.farthest_distance());
+	println!("Tiles: {}", network.count_inner_tiles(Strategy::RayCasting));
+
+	// The coloured loop is only drawn when asked for with `--render`
+	if std::env::args().any(|argument| argument == "--render") {
+		println!("{}", render_pipes(&network.classify(), RenderMode::Color));
+	}
 }
 
 #[cfg(test)]
@@ -205,9 +379,8 @@ L7JLJL-JLJLJL--JLJ.L";
 	#[test]
 	fn test_sample_1() {
 		let input = SAMPLE_1.to_string_vector();
-		let grid = Grid::from_string_vec(&input);
-		let start = Point::from(grid.find('S').unwrap());
-		let distance = traverse_pipes(start, &grid).0;
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
+		let distance = network.farthest_distance();
 
 		assert_eq!(distance, 4);
 	}
@@ -215,9 +388,8 @@ L7JLJL-JLJLJL--JLJ.L";
 	#[test]
 	fn test_sample_2() {
 		let input = SAMPLE_2.to_string_vector();
-		let grid = Grid::from_string_vec(&input);
-		let start = Point::from(grid.find('S').unwrap());
-		let distance = traverse_pipes(start, &grid).0;
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
+		let distance = network.farthest_distance();
 
 		assert_eq!(distance, 8);
 	}
@@ -225,9 +397,8 @@ L7JLJL-JLJLJL--JLJ.L";
 	#[test]
 	fn test_sample_3() {
 		let input = SAMPLE_3.to_string_vector();
-		let grid = Grid::from_string_vec(&input);
-		let start = Point::from(grid.find('S').unwrap());
-		let tiles = traverse_pipes(start, &grid).1;
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
+		let tiles = network.count_inner_tiles(Strategy::RayCasting);
 
 		assert_eq!(tiles, 4);
 	}
@@ -235,9 +406,8 @@ L7JLJL-JLJLJL--JLJ.L";
 	#[test]
 	fn test_sample_4() {
 		let input = SAMPLE_4.to_string_vector();
-		let grid = Grid::from_string_vec(&input);
-		let start = Point::from(grid.find('S').unwrap());
-		let tiles = traverse_pipes(start, &grid).1;
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
+		let tiles = network.count_inner_tiles(Strategy::RayCasting);
 
 		assert_eq!(tiles, 8);
 	}
@@ -245,13 +415,88 @@ L7JLJL-JLJLJL--JLJ.L";
 	#[test]
 	fn test_sample_5() {
 		let input = SAMPLE_5.to_string_vector();
-		let grid = Grid::from_string_vec(&input);
-		let start = Point::from(grid.find('S').unwrap());
-		let tiles = traverse_pipes(start, &grid).1;
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&input)).unwrap();
+		let tiles = network.count_inner_tiles(Strategy::RayCasting);
 
 		assert_eq!(tiles, 10);
 	}
 
+	#[test]
+	fn test_strategies_agree() {
+		let samples = [(SAMPLE_1, 1), (SAMPLE_2, 1), (SAMPLE_3, 4), (SAMPLE_4, 8), (SAMPLE_5, 10)];
+
+		for (sample, expected) in samples {
+			let network = PipeNetwork::parse(&Grid::from_string_vec(&sample.to_string_vector())).unwrap();
+
+			for strategy in [Strategy::RayCasting, Strategy::FloodFill, Strategy::Shoelace] {
+				assert_eq!(network.count_inner_tiles(strategy), expected, "{:?}", strategy);
+			}
+		}
+	}
+
+	#[test]
+	fn test_infer_start() {
+		let network_1 = PipeNetwork::parse(&Grid::from_string_vec(&SAMPLE_1.to_string_vector())).unwrap();
+		assert_eq!(network_1.start_tile, Tile::SouthEast);
+
+		let network_2 = PipeNetwork::parse(&Grid::from_string_vec(&SAMPLE_2.to_string_vector())).unwrap();
+		assert_eq!(network_2.start_tile, Tile::SouthEast);
+
+		let network_5 = PipeNetwork::parse(&Grid::from_string_vec(&SAMPLE_5.to_string_vector())).unwrap();
+		assert_eq!(network_5.start_tile, Tile::SouthWest);
+	}
+
+	#[test]
+	fn test_invalid_networks() {
+		let parse = |sample: &str| PipeNetwork::parse(&Grid::from_string_vec(&sample.to_string_vector()));
+
+		assert_eq!(
+			parse(".....\n.S-7.\n.|.|.\n.L-X.\n.....").unwrap_err(),
+			PipeError::UnknownTile { value: 'X', position: Point::new(3, 3) });
+
+		assert_eq!(parse(".....\n..-7.\n.|.|.\n.L-J.\n.....").unwrap_err(), PipeError::MissingStart);
+
+		assert_eq!(
+			parse(".....\n.S-7.\n.|...\n.L-J.\n.....").unwrap_err(),
+			PipeError::DeadEnd { position: Point::new(3, 3) });
+
+		assert_eq!(
+			parse(".|...\n-S-7.\n.|.|.\n.L-J.\n.....").unwrap_err(),
+			PipeError::Branching { position: Point::new(1, 1), connections: 4 });
+
+		assert_eq!(
+			parse(".....\n.S...\n.|...\n.L-..\n.....").unwrap_err(),
+			PipeError::DeadEnd { position: Point::new(1, 1) });
+	}
+
+	#[test]
+	fn test_render_loop() {
+		let network = PipeNetwork::parse(&Grid::from_string_vec(&SAMPLE_3.to_string_vector())).unwrap();
+		let rendered = render_pipes(&network.classify(), RenderMode::Plain);
+
+		let expected = "\
+OOOOOOOOOOO
+O┌───────┐O
+O│┌─────┐│O
+O││OOOOO││O
+O││OOOOO││O
+O│└─┐O┌─┘│O
+O│II│O│II│O
+O└──┘O└──┘O
+OOOOOOOOOOO
+";
+
+		assert_eq!(rendered, expected);
+	}
+
+	#[test]
+	fn test_render_colors() {
+		let grid = Grid::from_string_vec(&vec!["F7I".to_string()]);
+		let rendered = render_pipes(&grid, RenderMode::Color);
+
+		assert_eq!(rendered, "\x1b[1;33m┌\x1b[0m\x1b[1;33m┐\x1b[0m\x1b[1;32mI\x1b[0m\n");
+	}
+
 	#[test]
 	fn test_print_grid() {
 		let input = SAMPLE_1.to_string_vector();
@@ -2,11 +2,11 @@ use aoc_2023::utils::grid::Grid;
 use aoc_2023::utils::point::Point;
 use aoc_2023::utils::utils;
 
-fn find_voids<T>(data: T) -> Vec<i64> where T: IntoIterator<Item = Vec<char>> {
+fn find_voids<'a, T, S>(data: T) -> Vec<i64> where T: IntoIterator<Item = S>, S: IntoIterator<Item = &'a char> {
 	data.into_iter()
 		.enumerate()
 		.filter_map(|(index, sequence)| {
-			if sequence.iter().any(|&cell| cell != '.') {
+			if sequence.into_iter().any(|&cell| cell != '.') {
 				None
 			} else {
 				Some(index as i64)
@@ -15,12 +15,19 @@ fn find_voids<T>(data: T) -> Vec<i64> where T: IntoIterator<Item = Vec<char>> {
 		.collect()
 }
 
-fn expand_universe(universe: &mut Grid) {
-	let horizontal_voids = find_voids(universe.iter_rows());
-	let vertical_voids = find_voids(universe.iter_columns());
+// Number of voids strictly before each index, so a coordinate maps to its expanded one in O(1)
+fn prefix_voids(voids: &Vec<i64>, length: usize) -> Vec<i64> {
+	let mut prefix = vec![0; length + 1];
 
-	horizontal_voids.iter().rev().for_each(|&void_index| universe.insert_row_at(void_index as usize, '.'));
-	vertical_voids.iter().rev().for_each(|&void_index| universe.insert_column_at(void_index as usize, '.'));
+	for &void_index in voids {
+		prefix[void_index as usize + 1] += 1;
+	}
+
+	for i in 1 ..= length {
+		prefix[i] += prefix[i - 1];
+	}
+
+	prefix
 }
 
 fn find_galaxies(universe: &Grid) -> Vec<Point> {
@@ -31,59 +38,140 @@ fn find_galaxies(universe: &Grid) -> Vec<Point> {
 		.collect()
 }
 
-fn get_galaxy_distances(universe: &Grid) -> Vec<u64> {
-	let galaxies = find_galaxies(universe);
+#[derive(Debug)]
+struct Universe {
+	galaxies: Vec<Point>,
+	empty_rows: Vec<i64>,
+	empty_columns: Vec<i64>
+}
 
-	galaxies.iter().enumerate()
-		.flat_map(|(i, &p1)| galaxies.iter().enumerate()
-			.skip(i + 1)
-			.map(move |(_, &p2)| {
-				p1.manhattan_to(&p2)
-			}))
-		.collect()
+impl Universe {
+	fn from_grid(grid: &Grid) -> Self {
+		let (columns, rows) = grid.get_size();
+
+		Self {
+			galaxies: find_galaxies(grid),
+			empty_rows: prefix_voids(&find_voids(grid.rows()), rows),
+			empty_columns: prefix_voids(&find_voids(grid.columns()), columns)
+		}
+	}
+
+	fn expand(&self, galaxy: &Point, expansion_factor: u64) -> Point {
+		let growth = expansion_factor as i64 - 1;
+
+		Point::new(
+			galaxy.x + self.empty_rows[galaxy.x as usize] * growth,
+			galaxy.y + self.empty_columns[galaxy.y as usize] * growth)
+	}
+
+	fn expanded_galaxies(&self, expansion_factor: u64) -> Vec<Point> {
+		self.galaxies
+			.iter()
+			.map(|galaxy| self.expand(galaxy, expansion_factor))
+			.collect()
+	}
+}
+
+// Per-galaxy queries. The puzzle only asks for the total distance, so these are only used by the tests
+#[cfg_attr(not(test), allow(dead_code))]
+impl Universe {
+	// Galaxies are numbered from 1, in reading order, as in the puzzle statement
+	fn galaxy(&self, id: usize, expansion_factor: u64) -> Option<Point> {
+		id.checked_sub(1)
+			.and_then(|index| self.galaxies.get(index))
+			.map(|galaxy| self.expand(galaxy, expansion_factor))
+	}
+
+	fn distance(&self, lhs: usize, rhs: usize, expansion_factor: u64) -> Option<u64> {
+		let lhs = self.galaxy(lhs, expansion_factor)?;
+		let rhs = self.galaxy(rhs, expansion_factor)?;
+
+		Some(lhs.manhattan_to(&rhs))
+	}
+
+	// Every pair once as `(id, other_id, distance)` with `id < other_id`
+	fn pairs(&self, expansion_factor: u64) -> Vec<(usize, usize, u64)> {
+		let galaxies = self.expanded_galaxies(expansion_factor);
+
+		galaxies.iter().enumerate()
+			.flat_map(|(i, &p1)| galaxies.iter().enumerate()
+				.skip(i + 1)
+				.map(move |(j, &p2)| {
+					(i + 1, j + 1, p1.manhattan_to(&p2))
+				}))
+			.collect()
+	}
+
+	// The `k` closest galaxies to `id` as `(other_id, distance)`, closest first
+	fn nearest(&self, id: usize, k: usize, expansion_factor: u64) -> Vec<(usize, u64)> {
+		let Some(origin) = self.galaxy(id, expansion_factor) else {
+			return vec![]
+		};
+
+		let mut neighbours: Vec<_> = self.expanded_galaxies(expansion_factor)
+			.iter()
+			.enumerate()
+			.filter(|&(index, _)| index + 1 != id)
+			.map(|(index, galaxy)| (index + 1, origin.manhattan_to(galaxy)))
+			.collect();
+
+		neighbours.sort_by_key(|&(other, distance)| (distance, other));
+		neighbours.truncate(k);
+		neighbours
+	}
+
+	// Manhattan distance is the largest spread of either `x + y` or `x - y`, so only the
+	// extremes of both need to be tracked rather than every pair
+	fn farthest_pair(&self, expansion_factor: u64) -> Option<(usize, usize, u64)> {
+		let galaxies = self.expanded_galaxies(expansion_factor);
+
+		if galaxies.len() < 2 {
+			return None
+		}
+
+		[|galaxy: &Point| galaxy.x + galaxy.y, |galaxy: &Point| galaxy.x - galaxy.y]
+			.iter()
+			.filter_map(|key| {
+				let (min, lhs) = galaxies.iter().enumerate().map(|(index, galaxy)| (key(galaxy), index)).min()?;
+				let (max, rhs) = galaxies.iter().enumerate().map(|(index, galaxy)| (key(galaxy), std::cmp::Reverse(index))).max()?;
+
+				Some((lhs.min(rhs.0) + 1, lhs.max(rhs.0) + 1, (max - min) as u64))
+			})
+			.max_by_key(|&(lhs, rhs, distance)| (distance, std::cmp::Reverse((lhs, rhs))))
+	}
 }
 
-fn count_voids_between(points: &(i64, i64), voids: &Vec<i64>) -> u64 {
-	let (p1, p2) = points;
-	let (min_point, max_point) = (p1.min(&p2), p1.max(&p2));
+// Sum of |a - b| over every pair, by sorting and weighing each value against the ones before it
+fn sum_axis_distances(mut coordinates: Vec<i64>) -> u64 {
+	coordinates.sort_unstable();
 
-	voids.iter()
-		.filter(|&&x| {
-			(min_point .. max_point).contains(&&x)
+	let mut preceding_sum = 0;
+
+	coordinates.iter()
+		.enumerate()
+		.fold(0, |acc, (index, &coordinate)| {
+			let distance = coordinate * index as i64 - preceding_sum;
+			preceding_sum += coordinate;
+
+			acc + distance as u64
 		})
-		.count() as u64
 }
 
-fn get_galaxy_distances_expansion(universe: &Grid, expansion_factor: u32) -> Vec<u64> {
-	let horizontal_voids = &find_voids(universe.iter_rows());
-	let vertical_voids = &find_voids(universe.iter_columns());
-	let galaxies = find_galaxies(&universe);
-
-	galaxies.iter().enumerate()
-		.flat_map(|(i, &p1)| galaxies.iter().enumerate()
-			.skip(i + 1)
-			.map(move |(_, &p2)| {
-				let h_voids_between = count_voids_between(&(p1.x, p2.x), &horizontal_voids);
-				let v_voids_between = count_voids_between(&(p1.y, p2.y), &vertical_voids);
-
-				p1.manhattan_to(&p2) + (h_voids_between + v_voids_between) * (expansion_factor as u64 - 1)
-			}))
-		.collect::<Vec<_>>()
+fn sum_galaxy_distances(universe: &Universe, expansion_factor: u64) -> u64 {
+	let galaxies = universe.expanded_galaxies(expansion_factor);
+
+	sum_axis_distances(galaxies.iter().map(|galaxy| galaxy.x).collect())
+		+ sum_axis_distances(galaxies.iter().map(|galaxy| galaxy.y).collect())
 }
 
 fn part_1(input: &Vec<String>) -> u64 {
-	let mut universe = Grid::from_string_vec(&input);
-	expand_universe(&mut universe);
-
-	let distances = get_galaxy_distances(&universe);
-	distances.iter().fold(0, |acc, x| acc + x)
+	part_2(input, 2)
 }
 
 fn part_2(input: &Vec<String>, expansion_factor: u32) -> u64 {
-	let universe = Grid::from_string_vec(&input);
+	let universe = Universe::from_grid(&Grid::from_string_vec(input));
 
-	let distances = get_galaxy_distances_expansion(&universe, expansion_factor);
-	distances.iter().fold(0, |acc, &x| acc + x )
+	sum_galaxy_distances(&universe, expansion_factor as u64)
 }
 
 fn main() {
@@ -102,6 +190,13 @@ mod tests {
 	use aoc_2023::utils::utils::StringExtension;
 	use super::*;
 
+	fn get_galaxy_distances(universe: &Universe, expansion_factor: u64) -> Vec<u64> {
+		universe.pairs(expansion_factor)
+			.iter()
+			.map(|&(_, _, distance)| distance)
+			.collect()
+	}
+
 	const SAMPLE_1: &str = "\
 ...#......
 .......#..
@@ -117,15 +212,27 @@ mod tests {
 	#[test]
 	fn test_sample_1() {
 		let input = SAMPLE_1.to_string_vector();
-		let mut universe = Grid::from_string_vec(&input);
-		expand_universe(&mut universe);
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
 
-		let distances = get_galaxy_distances(&universe);
+		let distances = get_galaxy_distances(&universe, 2);
 		let distance_sum = distances.iter().fold(0, |acc, x| acc + x);
 
 		assert_eq!(distance_sum, 374);
 	}
 
+	#[test]
+	fn test_distance_sum_matches_pairwise() {
+		let input = SAMPLE_1.to_string_vector();
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
+
+		for expansion_factor in [1, 2, 10, 100] {
+			let pairwise: u64 = get_galaxy_distances(&universe, expansion_factor).iter().sum();
+			assert_eq!(sum_galaxy_distances(&universe, expansion_factor), pairwise);
+		}
+
+		assert_eq!(sum_axis_distances(vec![5, 1, 3, 3]), 4 + 2 + 2 + 2 + 2);
+	}
+
 	#[test]
 	fn test_grid_iter() {
 		let test = "\
@@ -136,12 +243,12 @@ mod tests {
 		let input = test.to_string_vector();
 		let mut grid = Grid::from_string_vec(&input);
 
-		for row in grid.iter_rows() {
+		for row in grid.rows() {
 			println!("{:?}", row);
 		}
 
-		for column in grid.iter_columns() {
-			println!("{:?}", column);
+		for column in grid.columns() {
+			println!("{:?}", column.collect::<Vec<_>>());
 		}
 
 		grid.insert_row_at(1, '.');
@@ -154,22 +261,24 @@ mod tests {
 	#[test]
 	fn test_find_voids_expand() {
 		let input = SAMPLE_1.to_string_vector();
-		let mut universe = Grid::from_string_vec(&input);
+		let grid = Grid::from_string_vec(&input);
 
-		println!("{}", universe);
+		assert_eq!(find_voids(grid.rows()), vec![3, 7]);
+		assert_eq!(find_voids(grid.columns()), vec![2, 5, 8]);
 
-		expand_universe(&mut universe);
+		let universe = Universe::from_grid(&grid);
+		assert_eq!(universe.empty_rows, vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2]);
+		assert_eq!(universe.empty_columns, vec![0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3]);
 
-		println!("{}", universe);
-		find_galaxies(&universe);
+		assert_eq!(universe.expand(&Point::new(9, 4), 2), Point::new(11, 5));
+		assert_eq!(universe.expand(&Point::new(9, 4), 10), Point::new(27, 13));
 	}
 
 	#[test]
 	fn test_find_distance() {
 		let input = SAMPLE_1.to_string_vector();
-		let mut universe = Grid::from_string_vec(&input);
-		expand_universe(&mut universe);
-		let galaxies = find_galaxies(&universe);
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
+		let galaxies = universe.expanded_galaxies(2);
 
 		let grid_distance_17 = galaxies[0] - galaxies[6];
 		assert_eq!(grid_distance_17.x.abs() + grid_distance_17.y.abs(), 15);
@@ -181,6 +290,48 @@ mod tests {
 		assert_eq!(grid_distance_89.x.abs() + grid_distance_89.y.abs(), 5);
 	}
 
+	#[test]
+	fn test_galaxy_distance() {
+		let input = SAMPLE_1.to_string_vector();
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
+
+		assert_eq!(universe.distance(1, 7, 2), Some(15));
+		assert_eq!(universe.distance(7, 1, 2), Some(15));
+		assert_eq!(universe.distance(3, 6, 2), Some(17));
+		assert_eq!(universe.distance(8, 9, 2), Some(5));
+		assert_eq!(universe.distance(5, 9, 2), Some(9));
+
+		assert_eq!(universe.distance(0, 1, 2), None);
+		assert_eq!(universe.distance(1, 10, 2), None);
+	}
+
+	#[test]
+	fn test_nearest_galaxies() {
+		let input = SAMPLE_1.to_string_vector();
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
+
+		assert_eq!(universe.nearest(8, 1, 2), vec![(9, 5)]);
+		assert_eq!(universe.nearest(1, 2, 2), vec![(2, 6), (3, 6)]);
+		assert_eq!(universe.nearest(1, 20, 2).len(), 8);
+		assert!(universe.nearest(12, 3, 2).is_empty());
+	}
+
+	#[test]
+	fn test_farthest_pair() {
+		let input = SAMPLE_1.to_string_vector();
+		let universe = Universe::from_grid(&Grid::from_string_vec(&input));
+
+		assert_eq!(universe.farthest_pair(2), Some((2, 8, 19)));
+
+		for expansion_factor in [1, 10, 100] {
+			let (_, _, farthest) = universe.farthest_pair(expansion_factor).unwrap();
+			assert_eq!(Some(farthest), get_galaxy_distances(&universe, expansion_factor).into_iter().max());
+		}
+
+		assert_eq!(Universe::from_grid(&Grid::empty()).farthest_pair(2), None);
+		assert_eq!(Universe::from_grid(&Grid::parse("..#\n...").unwrap()).farthest_pair(2), None);
+	}
+
 	#[test]
 	fn test_find_distance_2() {
 		let input = SAMPLE_1.to_string_vector();
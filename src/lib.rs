@@ -1,5 +1,15 @@
 pub mod utils {
 	pub mod utils;
 	pub mod grid;
+	pub mod components;
+	pub mod automaton;
+	pub mod cycle;
+	pub mod ray;
+	pub mod reflection;
+	pub mod pattern;
 	pub mod point;
+	pub mod rect;
+	pub mod sparse_grid;
+	pub mod bit_grid;
+	pub mod render;
 }
@@ -0,0 +1,179 @@
+use std::mem;
+
+use crate::utils::grid::{Grid, GridAccess};
+use crate::utils::point::{Direction, Direction8, Point};
+
+// What a rule sees of the grid around a cell, as it was before the current step
+pub struct Neighbourhood<'a> {
+	grid: &'a Grid,
+	pub position: Point,
+	pub value: char
+}
+
+impl Neighbourhood<'_> {
+	// `None` outside of the grid
+	pub fn get(&self, direction: Direction8) -> Option<char> {
+		self.grid.cell(&(self.position + direction.point()))
+	}
+
+	pub fn orthogonal(&self) -> impl Iterator<Item = char> + '_ {
+		Direction::ALL.iter().filter_map(|&direction| self.get(direction.into()))
+	}
+
+	pub fn all(&self) -> impl Iterator<Item = char> + '_ {
+		Direction8::ALL.iter().filter_map(|&direction| self.get(direction))
+	}
+
+	// Occurrences of `value` amongst the eight surrounding cells
+	pub fn count(&self, value: char) -> usize {
+		self.all().filter(|&neighbour| neighbour == value).count()
+	}
+}
+
+// Owns the grid together with its back buffer, so stepping repeatedly never allocates
+pub struct Automaton<R> {
+	front: Grid,
+	back: Grid,
+	rule: R
+}
+
+impl<R> Automaton<R> where R: Fn(&Neighbourhood) -> char {
+	pub fn new(grid: Grid, rule: R) -> Self {
+		Self {
+			back: grid.clone(),
+			front: grid,
+			rule
+		}
+	}
+
+	pub fn grid(&self) -> &Grid {
+		&self.front
+	}
+
+	pub fn into_grid(self) -> Grid {
+		self.front
+	}
+
+	// Applies the rule to every cell at once, reading the previous state and writing into the
+	// back buffer that then gets swapped in. Returns whether any cell changed
+	pub fn step(&mut self) -> bool {
+		let changed = self.front.step_into(&mut self.back, &self.rule);
+
+		mem::swap(&mut self.front, &mut self.back);
+		changed
+	}
+
+	// Steps up to `steps` times, stopping early once the grid reaches a fixed point.
+	// Returns the amount of steps that changed the grid
+	pub fn run(&mut self, steps: usize) -> usize {
+		for step in 0 .. steps {
+			if !self.front.step_into(&mut self.back, &self.rule) {
+				return step
+			}
+
+			mem::swap(&mut self.front, &mut self.back);
+		}
+
+		steps
+	}
+
+	pub fn run_until_stable(&mut self) -> usize {
+		self.run(usize::MAX)
+	}
+}
+
+impl Grid {
+	// Single step in place. This allocates a back buffer on every call, loops should go
+	// through an `Automaton` instead
+	pub fn step<R>(&mut self, rule: R) -> bool where R: Fn(&Neighbourhood) -> char {
+		self.with_automaton(rule, |automaton| automaton.step())
+	}
+
+	pub fn run<R>(&mut self, rule: R, steps: usize) -> usize where R: Fn(&Neighbourhood) -> char {
+		self.with_automaton(rule, |automaton| automaton.run(steps))
+	}
+
+	pub fn run_until_stable<R>(&mut self, rule: R) -> usize where R: Fn(&Neighbourhood) -> char {
+		self.with_automaton(rule, |automaton| automaton.run_until_stable())
+	}
+
+	fn with_automaton<R, F, T>(&mut self, rule: R, apply: F) -> T
+	where R: Fn(&Neighbourhood) -> char, F: FnOnce(&mut Automaton<R>) -> T {
+		let mut automaton = Automaton::new(mem::replace(self, Grid::empty()), rule);
+		let result = apply(&mut automaton);
+
+		*self = automaton.into_grid();
+		result
+	}
+
+	fn step_into<R>(&self, back: &mut Grid, rule: &R) -> bool where R: Fn(&Neighbourhood) -> char {
+		let mut changed = false;
+
+		for (position, &value) in self.enumerate() {
+			let next = rule(&Neighbourhood { grid: self, position, value });
+
+			changed |= next != value;
+			back.set_value(next, position.x as usize, position.y as usize);
+		}
+
+		changed
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn life(cell: &Neighbourhood) -> char {
+		match (cell.value, cell.count('#')) {
+			('#', 2) | (_, 3) => '#',
+			_ => '.'
+		}
+	}
+
+	#[test]
+	fn test_blinker() {
+		let horizontal = Grid::parse(".....\n.....\n.###.\n.....\n.....").unwrap();
+		let vertical = Grid::parse(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+
+		let mut grid = horizontal.clone();
+		assert!(grid.step(life));
+		assert_eq!(grid.as_view(), vertical);
+
+		assert_eq!(grid.run(life, 5), 5);
+		assert_eq!(grid.as_view(), horizontal);
+	}
+
+	#[test]
+	fn test_fixed_point() {
+		let spread = |cell: &Neighbourhood| {
+			if cell.value == '.' && cell.orthogonal().any(|neighbour| neighbour == '~') { '~' } else { cell.value }
+		};
+
+		let mut grid = Grid::parse("~..#.\n.#...").unwrap();
+
+		assert_eq!(grid.run_until_stable(spread), 6);
+		assert_eq!(grid.as_view(), Grid::parse("~~~#~\n~#~~~").unwrap());
+		assert!(!grid.step(spread));
+
+		let mut limited = Grid::parse("~..#.\n.#...").unwrap();
+		assert_eq!(limited.run(spread, 2), 2);
+		assert_eq!(limited.as_view(), Grid::parse("~~~#.\n~#...").unwrap());
+	}
+
+	#[test]
+	fn test_automaton() {
+		let horizontal = Grid::parse(".....\n.....\n.###.\n.....\n.....").unwrap();
+		let vertical = Grid::parse(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+
+		let mut automaton = Automaton::new(horizontal.clone(), life);
+
+		for step in 0 .. 4 {
+			assert!(automaton.step());
+			assert_eq!(automaton.grid(), if step % 2 == 0 { &vertical } else { &horizontal });
+		}
+
+		assert_eq!(automaton.run(3), 3);
+		assert_eq!(automaton.into_grid(), vertical);
+	}
+}
@@ -0,0 +1,290 @@
+use core::fmt;
+
+use crate::utils::grid::Grid;
+
+// Boolean grid storing one bit per cell, each row packed into its own run of 64-bit words
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+	words: Vec<u64>,
+	rows: usize,
+	columns: usize,
+	words_per_row: usize
+}
+
+impl BitGrid {
+	pub fn new(rows: usize, columns: usize) -> Self {
+		let words_per_row = columns.div_ceil(64);
+
+		Self {
+			words: vec![0; rows * words_per_row],
+			rows,
+			columns,
+			words_per_row
+		}
+	}
+
+	// Cells satisfying `predicate` are set
+	pub fn from_grid<P>(grid: &Grid, predicate: P) -> Self where P: Fn(char) -> bool {
+		let (columns, rows) = grid.get_size();
+		let mut bits = Self::new(rows, columns);
+
+		for (point, &value) in grid.enumerate() {
+			if predicate(value) {
+				bits.set(point.x as usize, point.y as usize, true);
+			}
+		}
+
+		bits
+	}
+
+	pub fn to_grid(&self, set: char, unset: char) -> Grid {
+		let mut grid = Grid::new(self.rows, self.columns, unset);
+
+		for x in 0 .. self.rows {
+			for y in 0 .. self.columns {
+				if self.get(x, y) {
+					grid.set_value(set, x, y);
+				}
+			}
+		}
+
+		grid
+	}
+
+	pub fn get_size(&self) -> (usize, usize) {
+		(self.columns, self.rows)
+	}
+
+	pub fn get(&self, x: usize, y: usize) -> bool {
+		if x >= self.rows || y >= self.columns {
+			return false
+		}
+
+		self.row(x)[y / 64] >> (y % 64) & 1 == 1
+	}
+
+	pub fn set(&mut self, x: usize, y: usize, value: bool) {
+		if x >= self.rows || y >= self.columns {
+			panic!("Coordinates don't exist ({}, {})", x, y);
+		}
+
+		let word = &mut self.row_mut(x)[y / 64];
+		let mask = 1 << (y % 64);
+
+		if value { *word |= mask } else { *word &= !mask }
+	}
+
+	pub fn row(&self, x: usize) -> &[u64] {
+		&self.words[x * self.words_per_row .. (x + 1) * self.words_per_row]
+	}
+
+	fn row_mut(&mut self, x: usize) -> &mut [u64] {
+		&mut self.words[x * self.words_per_row .. (x + 1) * self.words_per_row]
+	}
+
+	pub fn count_ones(&self) -> usize {
+		self.words.iter().map(|word| word.count_ones() as usize).sum()
+	}
+
+	pub fn count_row(&self, x: usize) -> usize {
+		self.row(x).iter().map(|word| word.count_ones() as usize).sum()
+	}
+
+	fn combine<F>(&self, other: &BitGrid, operation: F) -> Self where F: Fn(u64, u64) -> u64 {
+		assert_eq!(self.get_size(), other.get_size(), "Grids of different size");
+
+		Self {
+			words: self.words.iter().zip(&other.words).map(|(&lhs, &rhs)| operation(lhs, rhs)).collect(),
+			..*self
+		}
+	}
+
+	#[must_use]
+	pub fn and(&self, other: &BitGrid) -> Self {
+		self.combine(other, |lhs, rhs| lhs & rhs)
+	}
+
+	#[must_use]
+	pub fn or(&self, other: &BitGrid) -> Self {
+		self.combine(other, |lhs, rhs| lhs | rhs)
+	}
+
+	#[must_use]
+	pub fn xor(&self, other: &BitGrid) -> Self {
+		self.combine(other, |lhs, rhs| lhs ^ rhs)
+	}
+
+	#[must_use]
+	pub fn not(&self) -> Self {
+		let mut inverted = Self {
+			words: self.words.iter().map(|word| !word).collect(),
+			..*self
+		};
+
+		inverted.clear_padding();
+		inverted
+	}
+
+	// Unused bits past the last column of each row must stay unset
+	fn clear_padding(&mut self) {
+		let remainder = self.columns % 64;
+
+		if remainder == 0 {
+			return
+		}
+
+		let mask = (1u64 << remainder) - 1;
+
+		for x in 0 .. self.rows {
+			let last = self.words_per_row - 1;
+			self.row_mut(x)[last] &= mask;
+		}
+	}
+
+	// Every cell takes the value of the cell `amount` columns to its left (cells shifted in are unset)
+	#[must_use]
+	pub fn shift_east(&self, amount: usize) -> Self {
+		let mut shifted = Self::new(self.rows, self.columns);
+		let (word_shift, bit_shift) = (amount / 64, amount % 64);
+
+		for x in 0 .. self.rows {
+			let source = self.row(x);
+			let target = shifted.row_mut(x);
+
+			for (index, word) in target.iter_mut().enumerate().skip(word_shift) {
+				let from = index - word_shift;
+				*word = source[from] << bit_shift;
+
+				if bit_shift > 0 && from > 0 {
+					*word |= source[from - 1] >> (64 - bit_shift);
+				}
+			}
+		}
+
+		shifted.clear_padding();
+		shifted
+	}
+
+	// Every cell takes the value of the cell `amount` columns to its right (cells shifted in are unset)
+	#[must_use]
+	pub fn shift_west(&self, amount: usize) -> Self {
+		let mut shifted = Self::new(self.rows, self.columns);
+		let (word_shift, bit_shift) = (amount / 64, amount % 64);
+
+		for x in 0 .. self.rows {
+			let source = self.row(x);
+			let target = shifted.row_mut(x);
+
+			for (index, word) in target.iter_mut().enumerate().take(self.words_per_row.saturating_sub(word_shift)) {
+				let from = index + word_shift;
+				*word = source[from] >> bit_shift;
+
+				if bit_shift > 0 && from + 1 < self.words_per_row {
+					*word |= source[from + 1] << (64 - bit_shift);
+				}
+			}
+		}
+
+		shifted
+	}
+
+	// Every cell takes the value of the cell `amount` rows above it (cells shifted in are unset)
+	#[must_use]
+	pub fn shift_south(&self, amount: usize) -> Self {
+		let mut shifted = Self::new(self.rows, self.columns);
+		let offset = (amount * self.words_per_row).min(self.words.len());
+
+		shifted.words[offset ..].copy_from_slice(&self.words[.. self.words.len() - offset]);
+		shifted
+	}
+
+	// Every cell takes the value of the cell `amount` rows below it (cells shifted in are unset)
+	#[must_use]
+	pub fn shift_north(&self, amount: usize) -> Self {
+		let mut shifted = Self::new(self.rows, self.columns);
+		let offset = (amount * self.words_per_row).min(self.words.len());
+
+		shifted.words[.. self.words.len() - offset].copy_from_slice(&self.words[offset ..]);
+		shifted
+	}
+
+	// Cells with at least one set orthogonal neighbour
+	#[must_use]
+	pub fn orthogonal_neighbours(&self) -> Self {
+		self.shift_north(1)
+			.or(&self.shift_south(1))
+			.or(&self.shift_east(1))
+			.or(&self.shift_west(1))
+	}
+}
+
+impl fmt::Display for BitGrid {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.to_grid('#', '.'))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE: &str = "\
+#...#
+.#...
+...##";
+
+	fn bits_from(sample: &str) -> BitGrid {
+		BitGrid::from_grid(&Grid::parse(sample).unwrap(), |value| value == '#')
+	}
+
+	#[test]
+	fn test_conversion() {
+		let bits = bits_from(SAMPLE);
+
+		assert_eq!(bits.count_ones(), 5);
+		assert_eq!(bits.count_row(2), 2);
+		assert!(bits.get(1, 1));
+		assert!(!bits.get(1, 2));
+		assert!(!bits.get(7, 7));
+		assert_eq!(bits.to_grid('#', '.'), Grid::parse(SAMPLE).unwrap());
+	}
+
+	#[test]
+	fn test_bitwise() {
+		let lhs = bits_from("##..\n..##");
+		let rhs = bits_from("#.#.\n.#.#");
+
+		assert_eq!(lhs.and(&rhs), bits_from("#...\n...#"));
+		assert_eq!(lhs.or(&rhs), bits_from("###.\n.###"));
+		assert_eq!(lhs.xor(&rhs), bits_from(".##.\n.##."));
+		assert_eq!(lhs.not(), bits_from("..##\n##.."));
+		assert_eq!(lhs.not().count_ones(), 4);
+	}
+
+	#[test]
+	fn test_shifts() {
+		let bits = bits_from(SAMPLE);
+
+		assert_eq!(bits.shift_east(1), bits_from(".#...\n..#..\n....#"));
+		assert_eq!(bits.shift_west(1), bits_from("...#.\n#....\n..##."));
+		assert_eq!(bits.shift_south(1), bits_from(".....\n#...#\n.#..."));
+		assert_eq!(bits.shift_north(2), bits_from("...##\n.....\n....."));
+		assert_eq!(bits.shift_north(5).count_ones(), 0);
+		assert_eq!(bits.orthogonal_neighbours(), bits_from(".#.#.\n#.###\n.####"));
+	}
+
+	#[test]
+	fn test_wide_rows() {
+		let mut bits = BitGrid::new(2, 130);
+		bits.set(0, 63, true);
+		bits.set(1, 129, true);
+
+		assert!(bits.shift_east(1).get(0, 64));
+		assert_eq!(bits.shift_east(1).count_ones(), 1);
+		assert!(bits.shift_east(65).get(0, 128));
+		assert!(bits.shift_west(66).get(1, 63));
+		assert!(!bits.shift_west(64).get(0, 63));
+		assert_eq!(bits.not().count_ones(), 2 * 130 - 2);
+	}
+}
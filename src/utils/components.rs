@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+
+use crate::utils::grid::{Grid, GridAccess};
+use crate::utils::point::{Direction, Direction8, Point};
+use crate::utils::rect::Rect;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+	// Cells touching by a side
+	Four,
+	// Cells touching by a side or a corner
+	Eight
+}
+
+impl Connectivity {
+	fn offsets(&self) -> Vec<Point> {
+		match self {
+			Self::Four => Direction::ALL.iter().map(|direction| direction.point()).collect(),
+			Self::Eight => Direction8::ALL.iter().map(|direction| direction.point()).collect()
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+	pub label: usize,
+	// Value of the first cell of the region, in reading order
+	pub value: char,
+	pub cells: Vec<Point>,
+	pub bounds: Rect,
+	// Cell sides not shared with another cell of the region
+	pub perimeter: usize,
+	// Straight segments the perimeter is made of
+	pub sides: usize
+}
+
+impl Region {
+	pub fn area(&self) -> usize {
+		self.cells.len()
+	}
+}
+
+impl Grid {
+	// Regions of cells matching `predicate`, any two matching neighbours belonging to the same one
+	pub fn components<P>(&self, predicate: P, connectivity: Connectivity) -> Vec<Region> where P: Fn(char) -> bool {
+		self.components_by(predicate, |_, _| true, connectivity)
+	}
+
+	// Like `components`, but neighbours only join when `equivalent` holds for their values
+	pub fn components_by<P, E>(&self, predicate: P, equivalent: E, connectivity: Connectivity) -> Vec<Region>
+		where P: Fn(char) -> bool, E: Fn(char, char) -> bool {
+		let (columns, rows) = self.get_size();
+		let offsets = connectivity.offsets();
+		let index = |point: &Point| point.x as usize * columns + point.y as usize;
+
+		let mut labels: Vec<Option<usize>> = vec![None; rows * columns];
+		let mut regions = Vec::new();
+
+		for (start, &value) in self.enumerate() {
+			if labels[index(&start)].is_some() || !predicate(value) {
+				continue;
+			}
+
+			let label = regions.len();
+			let mut cells = vec![];
+			let mut queue = VecDeque::from([start]);
+			labels[index(&start)] = Some(label);
+
+			while let Some(current) = queue.pop_front() {
+				let current_value = self.cell(&current).unwrap();
+				cells.push(current);
+
+				for offset in &offsets {
+					let next = current + *offset;
+
+					let Some(next_value) = self.cell(&next) else {
+						continue
+					};
+
+					if labels[index(&next)].is_none() && predicate(next_value) && equivalent(current_value, next_value) {
+						labels[index(&next)] = Some(label);
+						queue.push_back(next);
+					}
+				}
+			}
+
+			let in_region = |point: Point| self.cell(&point).is_some() && labels[index(&point)] == Some(label);
+
+			regions.push(Region {
+				label,
+				value,
+				bounds: Rect::from_points(cells.iter().copied()).unwrap(),
+				perimeter: count_perimeter(&cells, &in_region),
+				sides: count_corners(&cells, &in_region),
+				cells
+			});
+		}
+
+		regions
+	}
+}
+
+fn count_perimeter<F>(cells: &[Point], in_region: &F) -> usize where F: Fn(Point) -> bool {
+	cells.iter()
+		.flat_map(|&cell| Direction::ALL.iter().map(move |direction| cell + direction.point()))
+		.filter(|&neighbour| !in_region(neighbour))
+		.count()
+}
+
+// A polygon has as many sides as corners. Each cell may hold an outer corner (both sides
+// outside the region) or an inner one (both sides inside, diagonal outside) per quadrant
+fn count_corners<F>(cells: &[Point], in_region: &F) -> usize where F: Fn(Point) -> bool {
+	cells.iter()
+		.map(|&cell| {
+			Direction::ALL
+				.iter()
+				.filter(|direction| {
+					let side = cell + direction.point();
+					let other_side = cell + direction.turn_right().point();
+					let diagonal = cell + direction.point() + direction.turn_right().point();
+
+					match (in_region(side), in_region(other_side)) {
+						(false, false) => true,
+						(true, true) => !in_region(diagonal),
+						_ => false
+					}
+				})
+				.count()
+		})
+		.sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE: &str = "\
+AAAA
+BBCD
+BBCC
+EEEC";
+
+	fn summary(regions: &[Region]) -> Vec<(char, usize, usize, usize)> {
+		regions.iter().map(|region| (region.value, region.area(), region.perimeter, region.sides)).collect()
+	}
+
+	#[test]
+	fn test_same_value_regions() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+		let regions = grid.components_by(|_| true, |lhs, rhs| lhs == rhs, Connectivity::Four);
+
+		assert_eq!(
+			summary(&regions),
+			vec![('A', 4, 10, 4), ('B', 4, 8, 4), ('C', 4, 10, 8), ('D', 1, 4, 4), ('E', 3, 8, 4)]);
+
+		assert_eq!(regions[2].bounds, Rect::new(Point::new(1, 2), Point::new(3, 3)));
+		assert_eq!(regions.iter().map(|region| region.label).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+	}
+
+	#[test]
+	fn test_inner_holes() {
+		let grid = Grid::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO").unwrap();
+		let regions = grid.components(|value| value == 'O', Connectivity::Four);
+
+		assert_eq!(summary(&regions), vec![('O', 21, 36, 20)]);
+	}
+
+	#[test]
+	fn test_connectivity() {
+		let grid = Grid::parse("#..\n.#.\n..#").unwrap();
+
+		assert_eq!(grid.components(|value| value == '#', Connectivity::Four).len(), 3);
+
+		let diagonal = grid.components(|value| value == '#', Connectivity::Eight);
+		assert_eq!(diagonal.len(), 1);
+		assert_eq!(diagonal[0].area(), 3);
+		assert_eq!(diagonal[0].perimeter, 12);
+		assert_eq!(diagonal[0].bounds, Rect::new(Point::new(0, 0), Point::new(2, 2)));
+	}
+}
@@ -0,0 +1,80 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle<S> {
+	// Steps before the first state that belongs to the cycle
+	pub tail: usize,
+	pub length: usize,
+	// State reached after the requested amount of steps
+	pub state: S
+}
+
+fn advance<S, F>(state: &S, steps: usize, step: &F) -> S where S: Clone, F: Fn(&S) -> S {
+	(0 .. steps).fold(state.clone(), |current, _| step(&current))
+}
+
+// Brent's algorithm: finds the cycle keeping only two states in memory, then jumps
+// ahead to the state after `steps` iterations. Never returns if `step` doesn't cycle
+pub fn detect_cycle<S, F>(initial: S, step: F, steps: usize) -> Cycle<S> where S: Clone + Eq, F: Fn(&S) -> S {
+	let mut power = 1;
+	let mut length = 1;
+	let mut tortoise = initial.clone();
+	let mut hare = step(&initial);
+
+	while tortoise != hare {
+		if power == length {
+			tortoise = hare.clone();
+			power *= 2;
+			length = 0;
+		}
+
+		hare = step(&hare);
+		length += 1;
+	}
+
+	let mut tail = 0;
+	let mut tortoise = initial.clone();
+	let mut hare = advance(&initial, length, &step);
+
+	while tortoise != hare {
+		tortoise = step(&tortoise);
+		hare = step(&hare);
+		tail += 1;
+	}
+
+	let remaining = if steps < tail { steps } else { tail + (steps - tail) % length };
+
+	Cycle {
+		tail,
+		length,
+		state: advance(&initial, remaining, &step)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashSet;
+
+	use super::*;
+	use crate::utils::grid::Grid;
+
+	#[test]
+	fn test_cycle_with_tail() {
+		let step = |&value: &u32| if value < 10 { value + 1 } else { 7 };
+
+		assert_eq!(detect_cycle(0, step, 1_000_000_000), Cycle { tail: 7, length: 4, state: 8 });
+		assert_eq!(detect_cycle(0, step, 5).state, 5);
+		assert_eq!(detect_cycle(9, step, 0), Cycle { tail: 0, length: 4, state: 9 });
+	}
+
+	#[test]
+	fn test_grid_cycle() {
+		let grid = Grid::parse("#..\n...").unwrap();
+		let cycle = detect_cycle(grid.clone(), |grid| grid.rotate_cw(), 1_000_000_001);
+
+		assert_eq!((cycle.tail, cycle.length), (0, 4));
+		assert_eq!(cycle.state, grid.rotate_cw());
+
+		let states: HashSet<Grid> = (0 .. 8).map(|steps| detect_cycle(grid.clone(), |grid| grid.rotate_cw(), steps).state).collect();
+		assert_eq!(states.len(), 4);
+	}
+}
@@ -1,6 +1,54 @@
 use core::fmt;
 
-#[derive(Debug, Clone)]
+use crate::utils::point::{Direction, Direction8, Point};
+use crate::utils::rect::Rect;
+
+// Read access shared by the dense `Grid` and the `SparseGrid`, with neighbour and search helpers on top
+pub trait GridAccess {
+	type Cell: Copy + PartialEq;
+
+	fn cell(&self, point: &Point) -> Option<Self::Cell>;
+
+	// Smallest rectangle holding every cell, `None` when there are none
+	fn bounds(&self) -> Option<Rect>;
+
+	fn neighbours(&self, point: &Point) -> Vec<(Point, Self::Cell)> {
+		Direction::ALL
+			.iter()
+			.map(|direction| *point + direction.point())
+			.filter_map(|neighbour| self.cell(&neighbour).map(|value| (neighbour, value)))
+			.collect()
+	}
+
+	fn neighbours8(&self, point: &Point) -> Vec<(Point, Self::Cell)> {
+		Direction8::ALL
+			.iter()
+			.map(|direction| *point + direction.point())
+			.filter_map(|neighbour| self.cell(&neighbour).map(|value| (neighbour, value)))
+			.collect()
+	}
+
+	// First position in reading order holding `value`
+	fn position_of(&self, value: Self::Cell) -> Option<Point> {
+		self.bounds()?
+			.points()
+			.find(|point| self.cell(point) == Some(value))
+	}
+
+	fn positions_of(&self, value: Self::Cell) -> Vec<Point> {
+		self.bounds()
+			.map(|bounds| bounds.points().filter(|point| self.cell(point) == Some(value)).collect())
+			.unwrap_or_default()
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GridError {
+	// `line` is 1-based and counts every line of the input
+	RaggedRow { line: usize, expected: usize, found: usize }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid {
 	data: Vec<char>,
 	rows: usize,
@@ -31,12 +79,17 @@ impl Grid {
 	#[must_use]
 	pub fn from_string_vec(vector: &Vec<String>) -> Self {
 		if vector.is_empty() {
-			Self::empty();
+			return Self::empty()
 		}
 
 		let height = vector.len();
-		let width = vector[0].len();
-		let cells = vector.into_iter().flat_map(|s| s.chars().collect::<Vec<_>>()).collect();
+		let width = vector[0].chars().count();
+
+		for (index, row) in vector.iter().enumerate() {
+			assert_eq!(row.chars().count(), width, "Row {} has a different length", index + 1);
+		}
+
+		let cells: Vec<char> = vector.iter().flat_map(|s| s.chars()).collect();
 
 		Self {
 			data: cells,
@@ -45,6 +98,45 @@ impl Grid {
 		}
 	}
 
+	pub fn parse(input: &str) -> Result<Self, GridError> {
+		Self::parse_with(input, |value| value)
+	}
+
+	// Parses one row per line, mapping every cell through `map`. Blank lines before and after
+	// the grid are ignored, but every other line must be as wide as the first one
+	pub fn parse_with<F>(input: &str, map: F) -> Result<Self, GridError> where F: Fn(char) -> char {
+		let lines: Vec<(usize, &str)> = input
+			.lines()
+			.enumerate()
+			.map(|(index, line)| (index + 1, line.strip_suffix('\r').unwrap_or(line)))
+			.skip_while(|(_, line)| line.is_empty())
+			.collect();
+
+		let Some(last) = lines.iter().rposition(|(_, line)| !line.is_empty()) else {
+			return Ok(Self::empty())
+		};
+
+		let lines = &lines[..= last];
+		let columns = lines[0].1.chars().count();
+		let mut data = Vec::with_capacity(columns * lines.len());
+
+		for &(line_number, line) in lines {
+			let length = line.chars().count();
+
+			if length != columns {
+				return Err(GridError::RaggedRow { line: line_number, expected: columns, found: length })
+			}
+
+			data.extend(line.chars().map(&map));
+		}
+
+		Ok(Self {
+			data,
+			rows: lines.len(),
+			columns
+		})
+	}
+
 	pub fn get_size(&self) -> (usize, usize) {
 		(self.columns, self.rows)
 	}
@@ -86,30 +178,39 @@ impl Grid {
 	}
 
 	pub fn find_all(&self, value: char) -> Vec<(i32, i32)> {
-		self.iter_rows()
-			.enumerate()
-			.flat_map(|(x, row)| {
-				row.iter()
-					.enumerate()
-					.filter_map(move |(y, &c)| (c == value)
-						.then(|| (x as i32, y as i32)))
-					.collect::<Vec<_>>()
-			})
-			.collect::<Vec<_>>()
-	}
-
-	// Custom iterator for iterating over columns
-	pub fn iter_columns(&self) -> impl Iterator<Item = Vec<char>> + '_ {
-		(0..self.columns).map(move |col| {
-			(0..self.rows).map(move |row| self.data[row * self.columns + col]).collect()
-		})
+		self.enumerate()
+			.filter(|&(_, &c)| c == value)
+			.map(|(point, _)| (point.x as i32, point.y as i32))
+			.collect()
 	}
 
-	// Custom iterator for iterating over rows
-	pub fn iter_rows(&self) -> impl Iterator<Item = Vec<char>> + '_ {
-		(0..self.rows).map(move |row| {
-			self.data[row * self.columns..(row + 1) * self.columns].to_vec()
-		})
+	pub fn row(&self, x: usize) -> &[char] {
+		&self.data[x * self.columns .. (x + 1) * self.columns]
+	}
+
+	// Strided walk over the cells of column `y`, top to bottom
+	pub fn column(&self, y: usize) -> impl Iterator<Item = &char> + '_ {
+		assert!(y < self.columns, "Column doesn't exist ({})", y);
+
+		self.data[y ..].iter().step_by(self.columns)
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &[char]> + '_ {
+		self.data.chunks_exact(self.columns.max(1))
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &char> + '_> + '_ {
+		(0 .. self.columns).map(move |y| self.column(y))
+	}
+
+	// Every cell along with its position, in reading order
+	pub fn enumerate(&self) -> impl Iterator<Item = (Point, &char)> + '_ {
+		let columns = self.columns;
+
+		self.data
+			.iter()
+			.enumerate()
+			.map(move |(index, value)| (Point::new((index / columns) as i64, (index % columns) as i64), value))
 	}
 }
 
@@ -141,18 +242,277 @@ impl Grid {
 }
 
 
-impl fmt::Display for Grid {
+// Transformations, always producing a new grid
+impl Grid {
+	fn from_fn<F>(rows: usize, columns: usize, value_at: F) -> Self where F: Fn(usize, usize) -> char {
+		Self {
+			data: (0 .. rows).flat_map(|x| (0 .. columns).map(move |y| (x, y))).map(|(x, y)| value_at(x, y)).collect(),
+			rows,
+			columns
+		}
+	}
+
+	#[must_use]
+	pub fn transpose(&self) -> Self {
+		Self::from_fn(self.columns, self.rows, |x, y| self.data[self.get_index(y, x)])
+	}
+
+	#[must_use]
+	pub fn rotate_cw(&self) -> Self {
+		Self::from_fn(self.columns, self.rows, |x, y| self.data[self.get_index(self.rows - 1 - y, x)])
+	}
+
+	#[must_use]
+	pub fn rotate_ccw(&self) -> Self {
+		Self::from_fn(self.columns, self.rows, |x, y| self.data[self.get_index(y, self.columns - 1 - x)])
+	}
+
+	// Mirrors left to right
+	#[must_use]
+	pub fn flip_horizontal(&self) -> Self {
+		Self::from_fn(self.rows, self.columns, |x, y| self.data[self.get_index(x, self.columns - 1 - y)])
+	}
+
+	// Mirrors top to bottom
+	#[must_use]
+	pub fn flip_vertical(&self) -> Self {
+		Self::from_fn(self.rows, self.columns, |x, y| self.data[self.get_index(self.rows - 1 - x, y)])
+	}
+
+	pub fn as_view(&self) -> GridView<'_> {
+		GridView {
+			grid: self,
+			origin: (0, 0),
+			rows: self.rows,
+			columns: self.columns
+		}
+	}
+
+	// Borrowed window over `region`, `None` if it doesn't fit in the grid
+	pub fn view(&self, region: Rect<usize>) -> Option<GridView<'_>> {
+		if region.max.x >= self.rows || region.max.y >= self.columns {
+			return None
+		}
+
+		Some(GridView {
+			grid: self,
+			origin: (region.min.x, region.min.y),
+			rows: region.height() as usize,
+			columns: region.width() as usize
+		})
+	}
+}
+
+
+// Rectangular window into a `Grid`, with coordinates relative to its top-left corner
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a> {
+	grid: &'a Grid,
+	origin: (usize, usize),
+	rows: usize,
+	columns: usize
+}
+
+impl<'a> GridView<'a> {
+	pub fn get_size(&self) -> (usize, usize) {
+		(self.columns, self.rows)
+	}
+
+	pub fn get_value(&self, x: usize, y: usize) -> Option<char> {
+		if x >= self.rows || y >= self.columns {
+			return None
+		}
+
+		self.grid.get_value(self.origin.0 + x, self.origin.1 + y)
+	}
+
+	pub fn row(&self, x: usize) -> &'a [char] {
+		assert!(x < self.rows, "Row doesn't exist ({})", x);
+
+		let start = self.grid.get_index(self.origin.0 + x, self.origin.1);
+		&self.grid.data[start .. start + self.columns]
+	}
+
+	pub fn column(&self, y: usize) -> impl Iterator<Item = &'a char> + 'a {
+		assert!(y < self.columns, "Column doesn't exist ({})", y);
+
+		let start = self.grid.get_index(self.origin.0, self.origin.1 + y);
+
+		self.grid.data[start ..]
+			.iter()
+			.step_by(self.grid.columns)
+			.take(self.rows)
+	}
+
+	pub fn rows(&self) -> impl Iterator<Item = &'a [char]> + '_ {
+		(0 .. self.rows).map(move |x| self.row(x))
+	}
+
+	pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &'a char> + 'a> + '_ {
+		(0 .. self.columns).map(move |y| self.column(y))
+	}
+
+	pub fn to_grid(&self) -> Grid {
+		Grid::from_fn(self.rows, self.columns, |x, y| self.get_value(x, y).unwrap())
+	}
+}
+
+impl PartialEq for GridView<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		self.get_size() == other.get_size() && self.rows().eq(other.rows())
+	}
+}
+
+impl PartialEq<Grid> for GridView<'_> {
+	fn eq(&self, other: &Grid) -> bool {
+		*self == other.as_view()
+	}
+}
+
+impl GridAccess for GridView<'_> {
+	type Cell = char;
+
+	fn cell(&self, point: &Point) -> Option<char> {
+		if point.x < 0 || point.y < 0 {
+			return None
+		}
+
+		self.get_value(point.x as usize, point.y as usize)
+	}
+
+	fn bounds(&self) -> Option<Rect> {
+		(self.rows > 0 && self.columns > 0)
+			.then(|| Rect::new(Point::new(0, 0), Point::new(self.rows as i64 - 1, self.columns as i64 - 1)))
+	}
+}
+
+impl fmt::Display for GridView<'_> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let mut buffer = "".to_string();
 
-		for x in 0 .. self.rows {
-			for y in 0 .. self.columns {
-				buffer.push_str(&self.get_value(x, y).unwrap().to_string()) ;
-			}
-
+		for row in self.rows() {
+			buffer.extend(row.iter());
 			buffer.push('\n');
 		}
 
 		writeln!(f, "{}", buffer)
 	}
-}
\ No newline at end of file
+}
+
+
+// A grid reads the same as a view over all of it
+impl GridAccess for Grid {
+	type Cell = char;
+
+	fn cell(&self, point: &Point) -> Option<char> {
+		self.as_view().cell(point)
+	}
+
+	fn bounds(&self) -> Option<Rect> {
+		self.as_view().bounds()
+	}
+}
+
+
+impl fmt::Display for Grid {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(&self.as_view(), f)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::utils::StringExtension;
+
+	fn grid_from(sample: &str) -> Grid {
+		Grid::from_string_vec(&sample.to_string_vector())
+	}
+
+	const SAMPLE: &str = "\
+123
+456";
+
+	#[test]
+	fn test_parse() {
+		let grid = Grid::parse("\n123\r\n456\r\n\n").unwrap();
+
+		assert_eq!(grid.get_size(), (3, 2));
+		assert_eq!(grid.as_view(), grid_from(SAMPLE));
+
+		let mapped = Grid::parse_with("#.\n.#", |value| if value == '#' { '1' } else { '0' }).unwrap();
+		assert_eq!(mapped.as_view(), grid_from("10\n01"));
+
+		assert_eq!(Grid::parse("").unwrap().get_size(), (0, 0));
+		assert_eq!(Grid::parse("\n\n").unwrap().get_size(), (0, 0));
+	}
+
+	#[test]
+	fn test_parse_ragged() {
+		assert_eq!(
+			Grid::parse("123\n45\n789").unwrap_err(),
+			GridError::RaggedRow { line: 2, expected: 3, found: 2 });
+
+		assert_eq!(
+			Grid::parse("\n123\n456\n\n789").unwrap_err(),
+			GridError::RaggedRow { line: 4, expected: 3, found: 0 });
+	}
+
+	#[test]
+	#[should_panic(expected = "Row 2 has a different length")]
+	fn test_from_ragged_vec() {
+		// Row lengths add up to a full 4x3 grid, so only a per-row check catches this
+		grid_from("1234\n12\n123456");
+	}
+
+	#[test]
+	fn test_from_empty_vec() {
+		assert_eq!(Grid::from_string_vec(&vec![]).get_size(), (0, 0));
+	}
+
+	#[test]
+	fn test_transformations() {
+		let grid = grid_from(SAMPLE);
+
+		assert_eq!(grid.transpose().as_view(), grid_from("14\n25\n36"));
+		assert_eq!(grid.rotate_cw().as_view(), grid_from("41\n52\n63"));
+		assert_eq!(grid.rotate_ccw().as_view(), grid_from("36\n25\n14"));
+		assert_eq!(grid.flip_horizontal().as_view(), grid_from("321\n654"));
+		assert_eq!(grid.flip_vertical().as_view(), grid_from("456\n123"));
+		assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw().as_view(), grid);
+		assert_eq!(grid.rotate_cw().rotate_ccw().as_view(), grid);
+	}
+
+	#[test]
+	fn test_row_column_access() {
+		let grid = grid_from("12345\n67890\nabcde");
+
+		assert_eq!(grid.row(1), &['6', '7', '8', '9', '0']);
+		assert_eq!(grid.column(3).collect::<String>(), "49d");
+		assert_eq!(grid.rows().count(), 3);
+		assert_eq!(grid.columns().map(|column| column.collect::<String>()).collect::<Vec<_>>(), vec!["16a", "27b", "38c", "49d", "50e"]);
+		assert_eq!(grid.enumerate().nth(7), Some((Point::new(1, 2), &'8')));
+		assert_eq!(grid.find_all('c'), vec![(2, 2)]);
+
+		let view = grid.view(Rect::new(Point::new(1, 1), Point::new(2, 3))).unwrap();
+		assert_eq!(view.row(1), &['b', 'c', 'd']);
+		assert_eq!(view.column(0).collect::<String>(), "7b");
+	}
+
+	#[test]
+	fn test_view() {
+		let grid = grid_from("12345\n67890\nabcde");
+		let view = grid.view(Rect::new(Point::new(1, 1), Point::new(2, 3))).unwrap();
+
+		assert_eq!(view.get_size(), (3, 2));
+		assert_eq!(view.get_value(1, 2), Some('d'));
+		assert_eq!(view.get_value(2, 0), None);
+		assert_eq!(view.rows().collect::<Vec<_>>(), vec![&['7', '8', '9'][..], &['b', 'c', 'd'][..]]);
+		assert_eq!(view.columns().map(|column| column.collect::<String>()).collect::<Vec<_>>(), vec!["7b", "8c", "9d"]);
+		assert_eq!(format!("{}", view), "789\nbcd\n\n");
+		assert_eq!(view, grid_from("789\nbcd"));
+		assert_eq!(view.to_grid().as_view(), view);
+		assert!(grid.view(Rect::new(Point::new(1, 1), Point::new(3, 3))).is_none());
+	}
+}
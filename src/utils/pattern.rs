@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+
+const ROW_BASE: u64 = 1_000_003;
+const COLUMN_BASE: u64 = 998_244_353;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+	Identity,
+	RotateCw,
+	Rotate180,
+	RotateCcw,
+	FlipHorizontal,
+	FlipVertical,
+	Transpose,
+	AntiTranspose
+}
+
+impl Orientation {
+	pub const ALL: [Orientation; 8] = [
+		Self::Identity,
+		Self::RotateCw,
+		Self::Rotate180,
+		Self::RotateCcw,
+		Self::FlipHorizontal,
+		Self::FlipVertical,
+		Self::Transpose,
+		Self::AntiTranspose
+	];
+
+	pub fn apply(&self, grid: &Grid) -> Grid {
+		match self {
+			Self::Identity => grid.clone(),
+			Self::RotateCw => grid.rotate_cw(),
+			Self::Rotate180 => grid.rotate_cw().rotate_cw(),
+			Self::RotateCcw => grid.rotate_ccw(),
+			Self::FlipHorizontal => grid.flip_horizontal(),
+			Self::FlipVertical => grid.flip_vertical(),
+			Self::Transpose => grid.transpose(),
+			Self::AntiTranspose => grid.rotate_cw().flip_vertical()
+		}
+	}
+}
+
+// Polynomial hashes of every `width` wide window of `values`, by position of its first value
+fn rolling_hashes<I>(values: I, width: usize, base: u64) -> Vec<u64> where I: Iterator<Item = u64> {
+	let values: Vec<u64> = values.collect();
+
+	if width == 0 || width > values.len() {
+		return vec![]
+	}
+
+	let highest = (1 .. width).fold(1u64, |power, _| power.wrapping_mul(base));
+	let mut hash = values[.. width].iter().fold(0u64, |hash, &value| hash.wrapping_mul(base).wrapping_add(value));
+	let mut hashes = vec![hash];
+
+	for index in width .. values.len() {
+		hash = hash
+			.wrapping_sub(values[index - width].wrapping_mul(highest))
+			.wrapping_mul(base)
+			.wrapping_add(values[index]);
+
+		hashes.push(hash);
+	}
+
+	hashes
+}
+
+impl Grid {
+	fn pattern_matches_at(&self, pattern: &Grid, x: usize, y: usize, wildcard: Option<char>) -> bool {
+		pattern.rows().enumerate().all(|(row, cells)| {
+			self.row(x + row)[y .. y + cells.len()]
+				.iter()
+				.zip(cells)
+				.all(|(value, expected)| Some(*expected) == wildcard || value == expected)
+		})
+	}
+
+	// Top-left corners of every exact occurrence of `pattern`, using a 2D rolling hash
+	pub fn find_pattern(&self, pattern: &Grid) -> Vec<Point> {
+		let (columns, rows) = self.get_size();
+		let (width, height) = pattern.get_size();
+
+		if width == 0 || height == 0 || width > columns || height > rows {
+			return vec![]
+		}
+
+		let window_hashes: Vec<Vec<u64>> = self.rows()
+			.map(|row| rolling_hashes(row.iter().map(|&value| value as u64), width, ROW_BASE))
+			.collect();
+
+		let pattern_hash = rolling_hashes(
+			pattern.rows().map(|row| rolling_hashes(row.iter().map(|&value| value as u64), width, ROW_BASE)[0]),
+			height,
+			COLUMN_BASE)[0];
+
+		let mut found = vec![];
+
+		for y in 0 ..= columns - width {
+			let column_hashes = rolling_hashes(window_hashes.iter().map(|hashes| hashes[y]), height, COLUMN_BASE);
+
+			for (x, &hash) in column_hashes.iter().enumerate() {
+				if hash == pattern_hash && self.pattern_matches_at(pattern, x, y, None) {
+					found.push(Point::new(x as i64, y as i64));
+				}
+			}
+		}
+
+		found.sort();
+		found
+	}
+
+	// Like `find_pattern`, but cells of the pattern holding `wildcard` match anything. Candidates
+	// come from the longest run of non-wildcard cells in the pattern, found by a rolling hash per row
+	pub fn find_pattern_with_wildcard(&self, pattern: &Grid, wildcard: char) -> Vec<Point> {
+		let (columns, rows) = self.get_size();
+		let (width, height) = pattern.get_size();
+
+		if width == 0 || height == 0 || width > columns || height > rows {
+			return vec![]
+		}
+
+		// (row, first column, length) of the longest run without wildcards
+		let anchor = pattern.rows()
+			.enumerate()
+			.flat_map(|(x, row)| {
+				row.split(|&value| value == wildcard)
+					.scan(0, move |start, run| {
+						let anchor = (x, *start, run.len());
+						*start += run.len() + 1;
+
+						Some(anchor)
+					})
+			})
+			.max_by_key(|&(x, start, length)| (length, std::cmp::Reverse((x, start))))
+			.filter(|&(_, _, length)| length > 0);
+
+		let Some((anchor_x, anchor_y, length)) = anchor else {
+			return (0 ..= rows - height)
+				.flat_map(|x| (0 ..= columns - width).map(move |y| Point::new(x as i64, y as i64)))
+				.collect()
+		};
+
+		let anchor_cells = &pattern.row(anchor_x)[anchor_y .. anchor_y + length];
+		let anchor_hash = rolling_hashes(anchor_cells.iter().map(|&value| value as u64), length, ROW_BASE)[0];
+		let mut found = vec![];
+
+		for x in anchor_x ..= rows - height + anchor_x {
+			let hashes = rolling_hashes(self.row(x).iter().map(|&value| value as u64), length, ROW_BASE);
+
+			for (y, &hash) in hashes.iter().enumerate().skip(anchor_y).take(columns - width + 1) {
+				let (top, left) = (x - anchor_x, y - anchor_y);
+
+				if hash == anchor_hash && self.pattern_matches_at(pattern, top, left, Some(wildcard)) {
+					found.push(Point::new(top as i64, left as i64));
+				}
+			}
+		}
+
+		found.sort();
+		found
+	}
+
+	// Occurrences of the pattern in any of its 8 rotations and reflections. Orientations
+	// leaving the pattern unchanged are only searched once
+	pub fn find_pattern_oriented(&self, pattern: &Grid, wildcard: Option<char>) -> Vec<(Point, Orientation)> {
+		let mut seen = HashSet::new();
+		let mut found = vec![];
+
+		for orientation in Orientation::ALL {
+			let oriented = orientation.apply(pattern);
+
+			if !seen.insert(oriented.clone()) {
+				continue;
+			}
+
+			let positions = match wildcard {
+				Some(wildcard) => self.find_pattern_with_wildcard(&oriented, wildcard),
+				None => self.find_pattern(&oriented)
+			};
+
+			found.extend(positions.into_iter().map(|position| (position, orientation)));
+		}
+
+		found
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE: &str = "\
+.#..#.
+.##.##
+...#..
+#.....
+##..#.
+.#..##";
+
+	#[test]
+	fn test_exact_pattern() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+		let pattern = Grid::parse("#.\n##").unwrap();
+
+		assert_eq!(grid.find_pattern(&pattern), vec![Point::new(0, 1), Point::new(0, 4), Point::new(3, 0), Point::new(4, 4)]);
+		assert!(grid.find_pattern(&Grid::parse("###").unwrap()).is_empty());
+		assert!(grid.find_pattern(&Grid::parse("#######").unwrap()).is_empty());
+	}
+
+	#[test]
+	fn test_wildcard_pattern() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+
+		assert_eq!(
+			grid.find_pattern_with_wildcard(&Grid::parse("#?\n##").unwrap(), '?'),
+			vec![Point::new(0, 1), Point::new(0, 4), Point::new(3, 0), Point::new(4, 4)]);
+
+		assert_eq!(
+			grid.find_pattern_with_wildcard(&Grid::parse("#??\n??#").unwrap(), '?'),
+			vec![Point::new(1, 1)]);
+
+		assert_eq!(grid.find_pattern_with_wildcard(&Grid::parse("??\n??").unwrap(), '?').len(), 25);
+	}
+
+	#[test]
+	fn test_oriented_pattern() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+		let found = grid.find_pattern_oriented(&Grid::parse("#.\n##").unwrap(), None);
+
+		assert_eq!(found.iter().filter(|(_, orientation)| *orientation == Orientation::Identity).count(), 4);
+		assert!(found.contains(&(Point::new(4, 0), Orientation::Rotate180)));
+		// Flipping vertically gives the same pattern as rotating clockwise
+		assert!(found.iter().all(|(_, orientation)| *orientation != Orientation::FlipVertical));
+
+		let symmetric = grid.find_pattern_oriented(&Grid::parse("##").unwrap(), None);
+		assert_eq!(symmetric.iter().filter(|(_, orientation)| *orientation == Orientation::RotateCw).count(), 5);
+		assert_eq!(symmetric.len(), 4 + 5);
+	}
+
+	#[test]
+	fn test_large_grid() {
+		let size = 1000;
+		let mut grid = Grid::new(size, size, '.');
+
+		for offset in [(10, 20), (500, 999 - 2), (997, 0)] {
+			grid.set_value('#', offset.0, offset.1);
+			grid.set_value('#', offset.0 + 1, offset.1 + 1);
+			grid.set_value('#', offset.0 + 2, offset.1 + 2);
+		}
+
+		let pattern = Grid::parse("#..\n.#.\n..#").unwrap();
+		assert_eq!(grid.find_pattern(&pattern), vec![Point::new(10, 20), Point::new(500, 997), Point::new(997, 0)]);
+	}
+}
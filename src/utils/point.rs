@@ -1,46 +1,127 @@
 use core::fmt;
-use std::ops::{Add, AddAssign, Sub, SubAssign, Mul};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, Div, Neg};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Point {
-	pub x: i64,
-	pub y: i64
+// Integer types usable as point coordinates
+pub trait Coordinate:
+	Copy + Ord + Hash + Default
+	+ Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+	+ AddAssign + SubAssign {
+	const ZERO: Self;
+	const ONE: Self;
+
+	fn abs_difference(self, other: Self) -> u64;
+	fn saturating_minus(self, other: Self) -> Self;
+	fn saturating_plus(self, other: Self) -> Self;
 }
 
+macro_rules! impl_coordinate {
+	($($type:ty),*) => {
+		$(
+			impl Coordinate for $type {
+				const ZERO: Self = 0;
+				const ONE: Self = 1;
 
-impl Point {
+				#[inline]
+				fn abs_difference(self, other: Self) -> u64 {
+					self.abs_diff(other) as u64
+				}
+
+				#[inline]
+				fn saturating_minus(self, other: Self) -> Self {
+					self.saturating_sub(other)
+				}
+
+				#[inline]
+				fn saturating_plus(self, other: Self) -> Self {
+					self.saturating_add(other)
+				}
+			}
+		)*
+	};
+}
+
+impl_coordinate!(i32, i64, isize, u32, u64, usize);
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = i64> {
+	pub x: T,
+	pub y: T
+}
+
+
+impl<T: Coordinate> Point<T> {
 	#[inline]
 	#[must_use]
-	pub fn new(x: i64, y: i64) -> Self {
+	pub fn new(x: T, y: T) -> Self {
 		Self { x, y }
 	}
 
 	#[inline]
 	#[must_use]
-	pub fn from(value: (i64, i64)) -> Self {
+	pub fn from(value: (T, T)) -> Self {
 		Self {
 			x: value.0,
 			y: value.1
 		}
 	}
 
+	pub fn manhattan_to(&self, other: &Point<T>) -> u64 {
+		self.x.abs_difference(other.x) + self.y.abs_difference(other.y)
+	}
+
+	// Moves in any of the 8 directions count as one step
+	pub fn chebyshev_to(&self, other: &Point<T>) -> u64 {
+		self.x.abs_difference(other.x).max(self.y.abs_difference(other.y))
+	}
+
+	pub fn euclidean_squared_to(&self, other: &Point<T>) -> u64 {
+		let dx = self.x.abs_difference(other.x);
+		let dy = self.y.abs_difference(other.y);
+
+		dx * dx + dy * dy
+	}
+
+	#[must_use]
+	pub fn min_components(&self, other: &Point<T>) -> Self {
+		Self::new(self.x.min(other.x), self.y.min(other.y))
+	}
+
+	#[must_use]
+	pub fn max_components(&self, other: &Point<T>) -> Self {
+		Self::new(self.x.max(other.x), self.y.max(other.y))
+	}
+}
+
+
+impl<T: Coordinate + Neg<Output = T>> Point<T> {
+	// Rotation around the origin with `x` growing downwards, so North turns into East
+	#[must_use]
+	pub fn rotate_cw(&self) -> Self {
+		Self::new(self.y, -self.x)
+	}
+
+	#[must_use]
+	pub fn rotate_ccw(&self) -> Self {
+		Self::new(-self.y, self.x)
+	}
+}
+
+
+impl Point {
 	pub fn move_to(&mut self, direction: &Direction) {
 		let point = direction.point();
 
 		*self += point;
 	}
-
-	pub fn manhattan_to(&self, other: &Point) -> u64 {
-		self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-	}
 }
 
 
-impl Add for Point {
+impl<T: Coordinate> Add for Point<T> {
 	type Output = Self;
 
 	#[inline]
-	#[must_use]
 	fn add(self, rhs: Self) -> Self::Output {
 		 Self {
 			x: self.x + rhs.x,
@@ -50,7 +131,7 @@ impl Add for Point {
 }
 
 
-impl AddAssign for Point {
+impl<T: Coordinate> AddAssign for Point<T> {
 	#[inline]
 	fn add_assign(&mut self, rhs: Self) {
 		 self.x += rhs.x;
@@ -59,11 +140,10 @@ impl AddAssign for Point {
 }
 
 
-impl Sub for Point {
+impl<T: Coordinate> Sub for Point<T> {
 	type Output = Self;
 
 	#[inline]
-	#[must_use]
 	fn sub(self, rhs: Self) -> Self::Output {
 		Self {
 			x: self.x - rhs.x,
@@ -73,7 +153,7 @@ impl Sub for Point {
 }
 
 
-impl SubAssign for Point {
+impl<T: Coordinate> SubAssign for Point<T> {
 	#[inline]
 	fn sub_assign(&mut self, rhs: Self) {
 		self.x -= rhs.x;
@@ -82,10 +162,10 @@ impl SubAssign for Point {
 }
 
 
-impl Mul<i64> for Point {
+impl<T: Coordinate> Mul<T> for Point<T> {
 	type Output = Self;
 
-	fn mul(self, scalar: i64) -> Self::Output {
+	fn mul(self, scalar: T) -> Self::Output {
 		Self {
 			x: self.x * scalar,
 			y: self.y * scalar
@@ -93,30 +173,401 @@ impl Mul<i64> for Point {
 	}
 }
 
-impl fmt::Display for Point {
+
+impl<T: Coordinate> Div<T> for Point<T> {
+	type Output = Self;
+
+	fn div(self, scalar: T) -> Self::Output {
+		Self {
+			x: self.x / scalar,
+			y: self.y / scalar
+		}
+	}
+}
+
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point<T> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self {
+			x: -self.x,
+			y: -self.y
+		}
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "({}, {})", self.x, self.y)
 	}
 }
 
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T = i64> {
+	pub x: T,
+	pub y: T,
+	pub z: T
+}
+
+
+impl<T: Coordinate> Point3<T> {
+	#[inline]
+	#[must_use]
+	pub fn new(x: T, y: T, z: T) -> Self {
+		Self { x, y, z }
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn from(value: (T, T, T)) -> Self {
+		Self {
+			x: value.0,
+			y: value.1,
+			z: value.2
+		}
+	}
+
+	pub fn manhattan_to(&self, other: &Point3<T>) -> u64 {
+		self.x.abs_difference(other.x) + self.y.abs_difference(other.y) + self.z.abs_difference(other.z)
+	}
+
+	pub fn chebyshev_to(&self, other: &Point3<T>) -> u64 {
+		self.x.abs_difference(other.x)
+			.max(self.y.abs_difference(other.y))
+			.max(self.z.abs_difference(other.z))
+	}
+
+	pub fn euclidean_squared_to(&self, other: &Point3<T>) -> u64 {
+		let dx = self.x.abs_difference(other.x);
+		let dy = self.y.abs_difference(other.y);
+		let dz = self.z.abs_difference(other.z);
+
+		dx * dx + dy * dy + dz * dz
+	}
+
+	#[must_use]
+	pub fn min_components(&self, other: &Point3<T>) -> Self {
+		Self::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+	}
+
+	#[must_use]
+	pub fn max_components(&self, other: &Point3<T>) -> Self {
+		Self::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+	}
+}
+
+
+impl<T: Coordinate> Add for Point3<T> {
+	type Output = Self;
+
+	#[inline]
+	fn add(self, rhs: Self) -> Self::Output {
+		Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+	}
+}
+
+
+impl<T: Coordinate> AddAssign for Point3<T> {
+	#[inline]
+	fn add_assign(&mut self, rhs: Self) {
+		self.x += rhs.x;
+		self.y += rhs.y;
+		self.z += rhs.z;
+	}
+}
+
+
+impl<T: Coordinate> Sub for Point3<T> {
+	type Output = Self;
+
+	#[inline]
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+	}
+}
+
+
+impl<T: Coordinate> SubAssign for Point3<T> {
+	#[inline]
+	fn sub_assign(&mut self, rhs: Self) {
+		self.x -= rhs.x;
+		self.y -= rhs.y;
+		self.z -= rhs.z;
+	}
+}
+
+
+impl<T: Coordinate> Mul<T> for Point3<T> {
+	type Output = Self;
+
+	fn mul(self, scalar: T) -> Self::Output {
+		Self::new(self.x * scalar, self.y * scalar, self.z * scalar)
+	}
+}
+
+
+impl<T: Coordinate> Div<T> for Point3<T> {
+	type Output = Self;
+
+	fn div(self, scalar: T) -> Self::Output {
+		Self::new(self.x / scalar, self.y / scalar, self.z / scalar)
+	}
+}
+
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point3<T> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::new(-self.x, -self.y, -self.z)
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "({}, {}, {})", self.x, self.y, self.z)
+	}
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
 	North,
-	South,
 	East,
+	South,
 	West
 }
 
 impl Direction {
+	// Clockwise, starting from North
+	pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
 	#[inline]
 	#[must_use]
 	pub fn point(&self) -> Point {
 		match self {
 			Self::North => Point::new(-1, 0),
-			&Self::South => Point::new(1, 0),
+			Self::South => Point::new(1, 0),
 			Self::East => Point::new(0, 1),
 			Self::West => Point::new(0, -1)
 		}
 	}
+
+	#[must_use]
+	pub fn opposite(&self) -> Self {
+		match self {
+			Self::North => Self::South,
+			Self::South => Self::North,
+			Self::East => Self::West,
+			Self::West => Self::East
+		}
+	}
+
+	#[must_use]
+	pub fn turn_left(&self) -> Self {
+		match self {
+			Self::North => Self::West,
+			Self::West => Self::South,
+			Self::South => Self::East,
+			Self::East => Self::North
+		}
+	}
+
+	#[must_use]
+	pub fn turn_right(&self) -> Self {
+		match self {
+			Self::North => Self::East,
+			Self::East => Self::South,
+			Self::South => Self::West,
+			Self::West => Self::North
+		}
+	}
+
+	// Accepts arrows (`^>v<`), compass points (`NESW`) and relative moves (`UDLR`)
+	pub fn from_char(value: char) -> Option<Self> {
+		match value {
+			'^' | 'N' | 'U' => Some(Self::North),
+			'v' | 'S' | 'D' => Some(Self::South),
+			'>' | 'E' | 'R' => Some(Self::East),
+			'<' | 'W' | 'L' => Some(Self::West),
+			_ => None
+		}
+	}
+
+	pub fn to_arrow(&self) -> char {
+		match self {
+			Self::North => '^',
+			Self::South => 'v',
+			Self::East => '>',
+			Self::West => '<'
+		}
+	}
+
+	pub fn to_compass(&self) -> char {
+		match self {
+			Self::North => 'N',
+			Self::South => 'S',
+			Self::East => 'E',
+			Self::West => 'W'
+		}
+	}
+
+	pub fn to_relative(&self) -> char {
+		match self {
+			Self::North => 'U',
+			Self::South => 'D',
+			Self::East => 'R',
+			Self::West => 'L'
+		}
+	}
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+	North,
+	NorthEast,
+	East,
+	SouthEast,
+	South,
+	SouthWest,
+	West,
+	NorthWest
+}
+
+impl Direction8 {
+	// Clockwise, starting from North
+	pub const ALL: [Direction8; 8] = [
+		Self::North,
+		Self::NorthEast,
+		Self::East,
+		Self::SouthEast,
+		Self::South,
+		Self::SouthWest,
+		Self::West,
+		Self::NorthWest
+	];
+
+	#[inline]
+	#[must_use]
+	pub fn point(&self) -> Point {
+		match self {
+			Self::North => Point::new(-1, 0),
+			Self::NorthEast => Point::new(-1, 1),
+			Self::East => Point::new(0, 1),
+			Self::SouthEast => Point::new(1, 1),
+			Self::South => Point::new(1, 0),
+			Self::SouthWest => Point::new(1, -1),
+			Self::West => Point::new(0, -1),
+			Self::NorthWest => Point::new(-1, -1)
+		}
+	}
+
+	fn index(&self) -> usize {
+		Self::ALL.iter().position(|direction| direction == self).unwrap()
+	}
+
+	#[must_use]
+	pub fn opposite(&self) -> Self {
+		Self::ALL[(self.index() + 4) % 8]
+	}
+
+	// Turns 45 degrees
+	#[must_use]
+	pub fn turn_left(&self) -> Self {
+		Self::ALL[(self.index() + 7) % 8]
+	}
+
+	// Turns 45 degrees
+	#[must_use]
+	pub fn turn_right(&self) -> Self {
+		Self::ALL[(self.index() + 1) % 8]
+	}
+
+	pub fn is_diagonal(&self) -> bool {
+		self.index() % 2 == 1
+	}
+}
+
+impl From<Direction> for Direction8 {
+	fn from(direction: Direction) -> Self {
+		match direction {
+			Direction::North => Self::North,
+			Direction::South => Self::South,
+			Direction::East => Self::East,
+			Direction::West => Self::West
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashSet;
+
+	use super::*;
+
+	#[test]
+	fn test_point_algebra() {
+		let p1 = Point::new(6, 1);
+		let p2 = Point::new(11, 5);
+
+		assert_eq!(p1.manhattan_to(&p2), 9);
+		assert_eq!(p1.chebyshev_to(&p2), 5);
+		assert_eq!(p1.euclidean_squared_to(&p2), 41);
+		assert_eq!(p1.min_components(&Point::new(8, -2)), Point::new(6, -2));
+		assert_eq!(p1.max_components(&Point::new(8, -2)), Point::new(8, 1));
+		assert_eq!(-p1, Point::new(-6, -1));
+		assert_eq!(p2 / 2, Point::new(5, 2));
+		assert_eq!(p1.rotate_cw(), Point::new(1, -6));
+		assert_eq!(p1.rotate_cw().rotate_ccw(), p1);
+
+		let unsigned: Point<usize> = Point::new(2, 9);
+		assert_eq!(unsigned.manhattan_to(&Point::new(4, 3)), 8);
+
+		let galaxies: HashSet<Point> = [p1, p2, p1].into_iter().collect();
+		assert_eq!(galaxies.len(), 2);
+
+		let mut sorted = vec![p2, p1, Point::new(6, 0)];
+		sorted.sort();
+		assert_eq!(sorted, vec![Point::new(6, 0), p1, p2]);
+
+		let q1 = Point3::new(1, 2, 3);
+		let q2 = Point3::new(-2, 6, 3);
+		assert_eq!(q1.manhattan_to(&q2), 7);
+		assert_eq!(q1.chebyshev_to(&q2), 4);
+		assert_eq!(q1.euclidean_squared_to(&q2), 25);
+		assert_eq!(q1 + q2, Point3::new(-1, 8, 6));
+		assert_eq!(-(q1 - q2), Point3::new(-3, 4, 0));
+	}
+
+	#[test]
+	fn test_directions() {
+		assert_eq!(Direction::North.turn_right(), Direction::East);
+		assert_eq!(Direction::North.turn_left(), Direction::West);
+		assert_eq!(Direction::West.opposite(), Direction::East);
+		assert!(Direction::ALL.iter().all(|direction| direction.turn_left().turn_right() == *direction));
+
+		assert_eq!(Direction::from_char('^'), Some(Direction::North));
+		assert_eq!(Direction::from_char('L'), Some(Direction::West));
+		assert_eq!(Direction::from_char('S'), Some(Direction::South));
+		assert_eq!(Direction::from_char('x'), None);
+		assert!(Direction::ALL.iter().all(|direction| {
+			[direction.to_arrow(), direction.to_compass(), direction.to_relative()]
+				.iter()
+				.all(|&value| Direction::from_char(value) == Some(*direction))
+		}));
+
+		let mut sorted = Direction::ALL.to_vec();
+		sorted.sort();
+		assert_eq!(sorted, Direction::ALL.to_vec());
+
+		assert_eq!(Direction8::NorthEast.opposite(), Direction8::SouthWest);
+		assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+		assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+		assert_eq!(Direction8::SouthEast.point(), Direction::South.point() + Direction::East.point());
+		assert_eq!(Direction8::from(Direction::West), Direction8::West);
+		assert_eq!(Direction8::ALL.iter().filter(|direction| direction.is_diagonal()).count(), 4);
+	}
 }
@@ -0,0 +1,121 @@
+use std::iter;
+
+use crate::utils::grid::Grid;
+use crate::utils::point::{Direction8, Point};
+
+impl Grid {
+	// Cells from the one next to `start` onwards in `direction`, until leaving the grid
+	pub fn ray<D>(&self, start: Point, direction: D) -> impl Iterator<Item = (Point, &char)> + '_ where D: Into<Direction8> {
+		let offset = direction.into().point();
+
+		iter::successors(Some(start + offset), move |&point| Some(point + offset))
+			.map_while(move |point| self.cell_ref(&point).map(|value| (point, value)))
+	}
+
+	// First cell along the ray satisfying `predicate`
+	pub fn walk_until<D, P>(&self, start: Point, direction: D, predicate: P) -> Option<(Point, &char)>
+		where D: Into<Direction8>, P: Fn(&char) -> bool {
+		self.ray(start, direction).find(|(_, value)| predicate(value))
+	}
+
+	// Cells on the straight segment from `from` to `to` (both included), by Bresenham's
+	// algorithm. Points falling outside the grid are skipped
+	pub fn line(&self, from: Point, to: Point) -> impl Iterator<Item = (Point, &char)> + '_ {
+		bresenham(from, to)
+			.into_iter()
+			.filter_map(move |point| self.cell_ref(&point).map(|value| (point, value)))
+	}
+
+	// Borrowed cell so rays and lines yield `&char` like `enumerate`, `None` outside the grid
+	fn cell_ref(&self, point: &Point) -> Option<&char> {
+		let (columns, rows) = self.get_size();
+
+		if point.x < 0 || point.y < 0 || point.x >= rows as i64 || point.y >= columns as i64 {
+			return None
+		}
+
+		self.row(point.x as usize).get(point.y as usize)
+	}
+}
+
+fn bresenham(from: Point, to: Point) -> Vec<Point> {
+	let dx = (to.x - from.x).abs();
+	let dy = -(to.y - from.y).abs();
+	let step = Point::new((to.x - from.x).signum(), (to.y - from.y).signum());
+
+	let mut points = vec![];
+	let mut current = from;
+	let mut error = dx + dy;
+
+	loop {
+		points.push(current);
+
+		if current == to {
+			return points
+		}
+
+		let doubled = 2 * error;
+
+		if doubled >= dy {
+			error += dy;
+			current.x += step.x;
+		}
+
+		if doubled <= dx {
+			error += dx;
+			current.y += step.y;
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::point::Direction;
+
+	const SAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390";
+
+	fn values<'a, I>(cells: I) -> String where I: Iterator<Item = (Point, &'a char)> {
+		cells.map(|(_, &value)| value).collect()
+	}
+
+	#[test]
+	fn test_ray() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+
+		assert_eq!(values(grid.ray(Point::new(2, 2), Direction::North)), "53");
+		assert_eq!(values(grid.ray(Point::new(2, 2), Direction::East)), "32");
+		assert_eq!(values(grid.ray(Point::new(2, 2), Direction8::SouthWest)), "33");
+		assert_eq!(values(grid.ray(Point::new(0, 0), Direction::West)), "");
+		assert_eq!(grid.ray(Point::new(0, 0), Direction8::SouthEast).last(), Some((Point::new(4, 4), &'0')));
+	}
+
+	#[test]
+	fn test_walk_until() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+		let tree = grid.row(3)[2];
+
+		assert_eq!(grid.walk_until(Point::new(3, 2), Direction::North, |&value| value >= tree), Some((Point::new(1, 2), &'5')));
+		assert_eq!(grid.walk_until(Point::new(3, 2), Direction::West, |&value| value >= tree), None);
+	}
+
+	#[test]
+	fn test_line() {
+		let grid = Grid::parse(SAMPLE).unwrap();
+		let points = |from, to| grid.line(from, to).map(|(point, _)| point).collect::<Vec<_>>();
+
+		assert_eq!(values(grid.line(Point::new(0, 0), Point::new(4, 4))), "35340");
+		assert_eq!(values(grid.line(Point::new(4, 0), Point::new(4, 3))), "3539");
+		assert_eq!(
+			points(Point::new(0, 0), Point::new(1, 4)),
+			vec![Point::new(0, 0), Point::new(0, 1), Point::new(1, 2), Point::new(1, 3), Point::new(1, 4)]);
+		assert_eq!(points(Point::new(-2, 0), Point::new(0, 0)), vec![Point::new(0, 0)]);
+		assert_eq!(points(Point::new(3, 3), Point::new(3, 3)), vec![Point::new(3, 3)]);
+	}
+}
@@ -0,0 +1,231 @@
+use std::iter;
+
+use crate::utils::grid::Grid;
+use crate::utils::point::{Coordinate, Point, Point3};
+
+// Inclusive range of coordinates from `from` to `to` (empty if `from > to`)
+fn coordinates<T: Coordinate>(from: T, to: T) -> impl Iterator<Item = T> + Clone {
+	iter::successors((from <= to).then_some(from), move |&value| (value < to).then(|| value + T::ONE))
+}
+
+// Axis-aligned rectangle, both corners included
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect<T = i64> {
+	pub min: Point<T>,
+	pub max: Point<T>
+}
+
+impl<T: Coordinate> Rect<T> {
+	#[must_use]
+	pub fn new(corner: Point<T>, opposite: Point<T>) -> Self {
+		Self {
+			min: corner.min_components(&opposite),
+			max: corner.max_components(&opposite)
+		}
+	}
+
+	// Bounding box of all points, `None` when there are none
+	pub fn from_points<I>(points: I) -> Option<Self> where I: IntoIterator<Item = Point<T>> {
+		points.into_iter().fold(None, |rect: Option<Self>, point| {
+			Some(match rect {
+				Some(rect) => Self::new(rect.min.min_components(&point), rect.max.max_components(&point)),
+				None => Self::new(point, point)
+			})
+		})
+	}
+
+	pub fn width(&self) -> u64 {
+		self.min.y.abs_difference(self.max.y) + 1
+	}
+
+	pub fn height(&self) -> u64 {
+		self.min.x.abs_difference(self.max.x) + 1
+	}
+
+	pub fn area(&self) -> u64 {
+		self.width() * self.height()
+	}
+
+	pub fn contains(&self, point: &Point<T>) -> bool {
+		(self.min.x ..= self.max.x).contains(&point.x) && (self.min.y ..= self.max.y).contains(&point.y)
+	}
+
+	pub fn intersect(&self, other: &Rect<T>) -> Option<Self> {
+		let min = self.min.max_components(&other.min);
+		let max = self.max.min_components(&other.max);
+
+		(min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+	}
+
+	// Grows the rectangle on every side, saturating at the limits of the coordinate type
+	#[must_use]
+	pub fn expand(&self, amount: T) -> Self {
+		Self {
+			min: Point::new(self.min.x.saturating_minus(amount), self.min.y.saturating_minus(amount)),
+			max: Point::new(self.max.x.saturating_plus(amount), self.max.y.saturating_plus(amount))
+		}
+	}
+
+	// Contained points in reading order
+	pub fn points(&self) -> impl Iterator<Item = Point<T>> {
+		let columns = coordinates(self.min.y, self.max.y);
+
+		coordinates(self.min.x, self.max.x)
+			.flat_map(move |x| columns.clone().map(move |y| Point::new(x, y)))
+	}
+
+	// Empty grid with one cell per contained point
+	pub fn to_grid(&self, default_value: char) -> Grid {
+		Grid::new(self.height() as usize, self.width() as usize, default_value)
+	}
+}
+
+impl Rect<usize> {
+	// Rectangle covering every cell of the grid, `None` for an empty grid
+	pub fn from_grid(grid: &Grid) -> Option<Self> {
+		let (columns, rows) = grid.get_size();
+
+		(columns > 0 && rows > 0).then(|| Self::new(Point::new(0, 0), Point::new(rows - 1, columns - 1)))
+	}
+}
+
+
+// Axis-aligned box, both corners included
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<T = i64> {
+	pub min: Point3<T>,
+	pub max: Point3<T>
+}
+
+impl<T: Coordinate> Cuboid<T> {
+	#[must_use]
+	pub fn new(corner: Point3<T>, opposite: Point3<T>) -> Self {
+		Self {
+			min: corner.min_components(&opposite),
+			max: corner.max_components(&opposite)
+		}
+	}
+
+	pub fn from_points<I>(points: I) -> Option<Self> where I: IntoIterator<Item = Point3<T>> {
+		points.into_iter().fold(None, |cuboid: Option<Self>, point| {
+			Some(match cuboid {
+				Some(cuboid) => Self::new(cuboid.min.min_components(&point), cuboid.max.max_components(&point)),
+				None => Self::new(point, point)
+			})
+		})
+	}
+
+	pub fn volume(&self) -> u64 {
+		(self.min.x.abs_difference(self.max.x) + 1)
+			* (self.min.y.abs_difference(self.max.y) + 1)
+			* (self.min.z.abs_difference(self.max.z) + 1)
+	}
+
+	pub fn contains(&self, point: &Point3<T>) -> bool {
+		(self.min.x ..= self.max.x).contains(&point.x)
+			&& (self.min.y ..= self.max.y).contains(&point.y)
+			&& (self.min.z ..= self.max.z).contains(&point.z)
+	}
+
+	pub fn intersect(&self, other: &Cuboid<T>) -> Option<Self> {
+		let min = self.min.max_components(&other.min);
+		let max = self.max.min_components(&other.max);
+
+		(min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+	}
+
+	// Grows the box on every side, saturating at the limits of the coordinate type
+	#[must_use]
+	pub fn expand(&self, amount: T) -> Self {
+		Self {
+			min: Point3::new(
+				self.min.x.saturating_minus(amount),
+				self.min.y.saturating_minus(amount),
+				self.min.z.saturating_minus(amount)),
+			max: Point3::new(
+				self.max.x.saturating_plus(amount),
+				self.max.y.saturating_plus(amount),
+				self.max.z.saturating_plus(amount))
+		}
+	}
+
+	pub fn points(&self) -> impl Iterator<Item = Point3<T>> {
+		let ys = coordinates(self.min.y, self.max.y);
+		let zs = coordinates(self.min.z, self.max.z);
+
+		coordinates(self.min.x, self.max.x).flat_map(move |x| {
+			let zs = zs.clone();
+			ys.clone().flat_map(move |y| zs.clone().map(move |z| Point3::new(x, y, z)))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::utils::StringExtension;
+
+	const SAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+	#[test]
+	fn test_rect() {
+		let rect = Rect::new(Point::new(1, 3), Point::new(0, 0));
+
+		assert_eq!(rect, Rect::new(Point::new(0, 0), Point::new(1, 3)));
+		assert_eq!((rect.height(), rect.width(), rect.area()), (2, 4, 8));
+		assert_eq!(rect.points().count(), 8);
+		assert_eq!(rect.points().last(), Some(Point::new(1, 3)));
+		assert!(rect.contains(&Point::new(1, 2)));
+		assert!(!rect.contains(&Point::new(2, 2)));
+		assert_eq!(rect.expand(1), Rect::new(Point::new(-1, -1), Point::new(2, 4)));
+
+		let edge: Rect<usize> = Rect::new(Point::new(0, 1), Point::new(usize::MAX, 2));
+		assert_eq!(edge.expand(2), Rect::new(Point::new(0, 0), Point::new(usize::MAX, 4)));
+	}
+
+	#[test]
+	fn test_rect_from_grid() {
+		let grid = Grid::from_string_vec(&SAMPLE.to_string_vector());
+		let grid_bounds = Rect::from_grid(&grid).unwrap();
+
+		assert_eq!((grid_bounds.height(), grid_bounds.width()), (10, 10));
+		assert_eq!(grid_bounds.intersect(&Rect::new(Point::new(8, 8), Point::new(20, 9))), Some(Rect::new(Point::new(8, 8), Point::new(9, 9))));
+		assert_eq!(grid_bounds.intersect(&Rect::new(Point::new(10, 0), Point::new(12, 3))), None);
+		assert_eq!(Rect::new(Point::new(2, 1), Point::new(4, 3)).to_grid('.').get_size(), (3, 3));
+		assert_eq!(Rect::from_grid(&Grid::empty()), None);
+	}
+
+	#[test]
+	fn test_rect_from_points() {
+		let symbols: Vec<Point<usize>> = vec![Point::new(1, 3), Point::new(3, 6), Point::new(8, 3), Point::new(8, 5)];
+
+		assert_eq!(Rect::from_points(symbols), Some(Rect::new(Point::new(1, 3), Point::new(8, 6))));
+		assert_eq!(Rect::<i64>::from_points(vec![]), None);
+	}
+
+	#[test]
+	fn test_cuboid() {
+		let cuboid = Cuboid::from_points(vec![Point3::new(1, 2, 3), Point3::new(-1, 4, 3)]).unwrap();
+
+		assert_eq!(cuboid, Cuboid::new(Point3::new(-1, 2, 3), Point3::new(1, 4, 3)));
+		assert_eq!(cuboid.volume(), 9);
+		assert_eq!(cuboid.points().count(), 9);
+		assert!(cuboid.contains(&Point3::new(0, 3, 3)));
+		assert!(!cuboid.contains(&Point3::new(0, 3, 4)));
+		assert!(cuboid.expand(1).contains(&Point3::new(0, 3, 4)));
+		assert_eq!(cuboid.expand(1).volume(), 75);
+		assert_eq!(
+			cuboid.intersect(&Cuboid::new(Point3::new(1, 4, 0), Point3::new(5, 5, 5))),
+			Some(Cuboid::new(Point3::new(1, 4, 3), Point3::new(1, 4, 3))));
+	}
+}
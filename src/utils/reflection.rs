@@ -0,0 +1,98 @@
+use crate::utils::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reflection {
+	// Lines (rows or columns) before the mirror
+	pub axis: usize,
+	// Cells that differ from their mirrored counterpart
+	pub mismatches: usize
+}
+
+// Mirror axes for `length` lines where `mismatches(lhs, rhs)` compares two of them
+fn reflections<F>(length: usize, max_mismatches: usize, mismatches: F) -> Vec<Reflection> where F: Fn(usize, usize) -> usize {
+	(1 .. length)
+		.filter_map(|axis| {
+			let mut total = 0;
+
+			for offset in 0 .. axis.min(length - axis) {
+				total += mismatches(axis - 1 - offset, axis + offset);
+
+				if total > max_mismatches {
+					return None
+				}
+			}
+
+			Some(Reflection { axis, mismatches: total })
+		})
+		.collect()
+}
+
+impl Grid {
+	// Mirrors between rows, allowing up to `max_mismatches` differing cells
+	pub fn horizontal_reflections(&self, max_mismatches: usize) -> Vec<Reflection> {
+		let rows = self.get_size().1;
+
+		reflections(rows, max_mismatches, |lhs, rhs| {
+			self.row(lhs).iter().zip(self.row(rhs)).filter(|(a, b)| a != b).count()
+		})
+	}
+
+	// Mirrors between columns, allowing up to `max_mismatches` differing cells
+	pub fn vertical_reflections(&self, max_mismatches: usize) -> Vec<Reflection> {
+		let columns = self.get_size().0;
+
+		reflections(columns, max_mismatches, |lhs, rhs| {
+			self.column(lhs).zip(self.column(rhs)).filter(|(a, b)| a != b).count()
+		})
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE_1: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.";
+
+	const SAMPLE_2: &str = "\
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+	#[test]
+	fn test_perfect_reflections() {
+		let grid_1 = Grid::parse(SAMPLE_1).unwrap();
+		assert_eq!(grid_1.vertical_reflections(0), vec![Reflection { axis: 5, mismatches: 0 }]);
+		assert!(grid_1.horizontal_reflections(0).is_empty());
+
+		let grid_2 = Grid::parse(SAMPLE_2).unwrap();
+		assert_eq!(grid_2.horizontal_reflections(0), vec![Reflection { axis: 4, mismatches: 0 }]);
+		assert!(grid_2.vertical_reflections(0).is_empty());
+	}
+
+	#[test]
+	fn test_smudged_reflections() {
+		let smudged = |reflections: Vec<Reflection>| -> Vec<usize> {
+			reflections.iter().filter(|reflection| reflection.mismatches == 1).map(|reflection| reflection.axis).collect()
+		};
+
+		let grid_1 = Grid::parse(SAMPLE_1).unwrap();
+		assert_eq!(smudged(grid_1.horizontal_reflections(1)), vec![3]);
+		assert!(smudged(grid_1.vertical_reflections(1)).is_empty());
+
+		let grid_2 = Grid::parse(SAMPLE_2).unwrap();
+		assert_eq!(smudged(grid_2.horizontal_reflections(1)), vec![1]);
+		assert!(grid_2.horizontal_reflections(1).contains(&Reflection { axis: 4, mismatches: 0 }));
+	}
+}
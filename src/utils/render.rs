@@ -0,0 +1,60 @@
+use crate::utils::grid::Grid;
+
+const RESET: &str = "\x1b[0m";
+const LOOP_COLOR: &str = "\x1b[1;33m";
+const INSIDE_COLOR: &str = "\x1b[1;32m";
+const OUTSIDE_COLOR: &str = "\x1b[2;34m";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+	// Box drawing only, stable output for snapshot tests
+	Plain,
+	// Box drawing with ANSI colours for the terminal
+	Color
+}
+
+pub fn box_drawing(value: char) -> char {
+	match value {
+		'L' => '└',
+		'J' => '┘',
+		'7' => '┐',
+		'F' => '┌',
+		'|' => '│',
+		'-' => '─',
+		_ => value
+	}
+}
+
+fn color_for(value: char) -> Option<&'static str> {
+	match value {
+		'L' | 'J' | '7' | 'F' | '|' | '-' | 'S' => Some(LOOP_COLOR),
+		'I' => Some(INSIDE_COLOR),
+		'O' => Some(OUTSIDE_COLOR),
+		_ => None
+	}
+}
+
+// Renders a pipe grid where loop tiles keep their pipe glyph and the rest
+// of tiles are marked as inside (`I`) or outside (`O`) of the loop
+pub fn render_pipes(grid: &Grid, mode: RenderMode) -> String {
+	let mut buffer = String::new();
+
+	for row in grid.rows() {
+		for &value in row {
+			let glyph = box_drawing(value);
+
+			match (mode, color_for(value)) {
+				(RenderMode::Color, Some(color)) => {
+					buffer.push_str(color);
+					buffer.push(glyph);
+					buffer.push_str(RESET);
+				},
+				_ => buffer.push(glyph)
+			}
+		}
+
+		buffer.push('\n');
+	}
+
+	buffer
+}
@@ -0,0 +1,206 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use crate::utils::grid::{Grid, GridAccess};
+use crate::utils::point::Point;
+use crate::utils::rect::Rect;
+
+// Unbounded grid only storing the cells that were set, every other cell holds the default value
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+	cells: HashMap<Point, T>,
+	default_value: T,
+	bounds: Option<Rect>
+}
+
+impl<T: Copy + PartialEq> SparseGrid<T> {
+	pub fn new(default_value: T) -> Self {
+		Self {
+			cells: HashMap::new(),
+			default_value,
+			bounds: None
+		}
+	}
+
+	pub fn get_value(&self, point: &Point) -> T {
+		self.cells.get(point).copied().unwrap_or(self.default_value)
+	}
+
+	// Bounds grow to include `point`, even when `value` is the default one
+	pub fn set_value(&mut self, value: T, point: Point) {
+		self.cells.insert(point, value);
+
+		self.bounds = Some(match self.bounds {
+			Some(bounds) => Rect::new(bounds.min.min_components(&point), bounds.max.max_components(&point)),
+			None => Rect::new(point, point)
+		});
+	}
+
+	// Bounds are only recomputed from the remaining cells when `point` was on their edge
+	pub fn remove(&mut self, point: &Point) -> Option<T> {
+		let value = self.cells.remove(point)?;
+
+		if let Some(bounds) = self.bounds {
+			let on_edge = point.x == bounds.min.x || point.x == bounds.max.x
+				|| point.y == bounds.min.y || point.y == bounds.max.y;
+
+			if on_edge {
+				self.bounds = Rect::from_points(self.cells.keys().copied());
+			}
+		}
+
+		Some(value)
+	}
+
+	pub fn len(&self) -> usize {
+		self.cells.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.cells.is_empty()
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+		self.cells.iter()
+	}
+}
+
+impl SparseGrid<char> {
+	// Keeps only the cells that differ from `default_value`, but the bounds of the whole grid
+	// so that `to_grid` gives back the same size and origin
+	pub fn from_grid(grid: &Grid, default_value: char) -> Self {
+		let mut sparse = Self::new(default_value);
+
+		if let Some(bounds) = grid.bounds() {
+			for point in bounds.points() {
+				let value = grid.cell(&point).unwrap();
+
+				if value != default_value {
+					sparse.set_value(value, point);
+				}
+			}
+		}
+
+		sparse.bounds = grid.bounds();
+		sparse
+	}
+
+	// Dense copy of the cells within bounds, the top-left cell being `bounds.min`
+	pub fn to_grid(&self) -> Grid {
+		let Some(bounds) = self.bounds else {
+			return Grid::empty()
+		};
+
+		let mut grid = bounds.to_grid(self.default_value);
+
+		for (point, &value) in &self.cells {
+			let offset = *point - bounds.min;
+			grid.set_value(value, offset.x as usize, offset.y as usize);
+		}
+
+		grid
+	}
+}
+
+impl<T: Copy + PartialEq> GridAccess for SparseGrid<T> {
+	type Cell = T;
+
+	fn cell(&self, point: &Point) -> Option<T> {
+		Some(self.get_value(point))
+	}
+
+	fn bounds(&self) -> Option<Rect> {
+		self.bounds
+	}
+}
+
+impl<T: Copy + PartialEq + fmt::Display> fmt::Display for SparseGrid<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut buffer = "".to_string();
+
+		if let Some(bounds) = self.bounds {
+			for x in bounds.min.x ..= bounds.max.x {
+				for y in bounds.min.y ..= bounds.max.y {
+					buffer.push_str(&self.get_value(&Point::new(x, y)).to_string());
+				}
+
+				buffer.push('\n');
+			}
+		}
+
+		writeln!(f, "{}", buffer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::utils::utils::StringExtension;
+
+	const SAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+	#[test]
+	fn test_from_grid() {
+		let grid = Grid::from_string_vec(&SAMPLE.to_string_vector());
+		let sparse = SparseGrid::from_grid(&grid, '.');
+
+		assert_eq!(sparse.len(), 9);
+		assert_eq!(sparse.get_value(&Point::new(0, 3)), '#');
+		assert_eq!(sparse.get_value(&Point::new(-40, 70)), '.');
+		assert_eq!(format!("{}", sparse.to_grid()), format!("{}", grid));
+	}
+
+	#[test]
+	fn test_round_trip_keeps_empty_border() {
+		let grid = Grid::parse(".....\n..#..\n.....").unwrap();
+		let sparse = SparseGrid::from_grid(&grid, '.');
+
+		assert_eq!(sparse.len(), 1);
+		assert_eq!(sparse.bounds(), Some(Rect::new(Point::new(0, 0), Point::new(2, 4))));
+		assert_eq!(sparse.to_grid(), grid);
+
+		let empty = Grid::parse("...\n...").unwrap();
+		assert_eq!(SparseGrid::from_grid(&empty, '.').to_grid(), empty);
+	}
+
+	#[test]
+	fn test_grid_access() {
+		let grid = Grid::from_string_vec(&SAMPLE.to_string_vector());
+		let sparse = SparseGrid::from_grid(&grid, '.');
+
+		assert_eq!(sparse.position_of('#'), Some(Point::new(0, 3)));
+		assert_eq!(sparse.positions_of('#').len(), 9);
+		assert_eq!(grid.position_of('#'), Some(Point::new(0, 3)));
+		assert_eq!(grid.neighbours(&Point::new(0, 0)).len(), 2);
+		assert_eq!(sparse.neighbours8(&Point::new(0, 0)).len(), 8);
+	}
+
+	#[test]
+	fn test_bounds() {
+		let grid = Grid::from_string_vec(&SAMPLE.to_string_vector());
+		let mut sparse = SparseGrid::from_grid(&grid, '.');
+
+		sparse.set_value('#', Point::new(-1, 12));
+		assert_eq!(sparse.bounds(), Some(Rect::new(Point::new(-1, 0), Point::new(9, 12))));
+		assert_eq!(sparse.to_grid().get_size(), (13, 11));
+
+		sparse.remove(&Point::new(4, 6));
+		assert_eq!(sparse.bounds(), Some(Rect::new(Point::new(-1, 0), Point::new(9, 12))));
+
+		sparse.remove(&Point::new(-1, 12));
+		assert_eq!(sparse.bounds(), Some(Rect::new(Point::new(0, 0), Point::new(9, 9))));
+
+		assert_eq!(sparse.remove(&Point::new(-1, 12)), None);
+		assert_eq!(sparse.len(), 8);
+	}
+}